@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use nom::{
+    bytes::complete::is_not,
+    character::complete::{self, space1},
+    combinator::map,
+    sequence::tuple,
+};
+
+use crate::parse::must_parse_lines;
+
+pub const DEFAULT_PATH: &str = "inputs/answers.txt";
+
+// Known-good answers, one per line: `<day> <part> <answer>`. Blank lines and lines starting with
+// `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Self> {
+        let input = std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read answers file: {path}"))?;
+        Self::parse(&input).with_context(|| format!("invalid answers file: {path}"))
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let parse_line = map(
+            tuple((complete::u8, space1, complete::u8, space1, is_not("\n"))),
+            |(day, _, part, _, answer)| ((day, part), str::trim(answer).to_string()),
+        );
+        let answers = must_parse_lines(parse_line, input)?;
+        Ok(Self(answers.into_iter().collect()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: u8, actual: &str) -> Check {
+        match self.get(day, part) {
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_string(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"# day part answer
+1 1 142
+1 2 281
+
+7 2 5905"#;
+
+    #[test]
+    fn test_check() -> Result<()> {
+        let answers = Answers::parse(INPUT)?;
+        assert_eq!(answers.check(1, 1, "142"), Check::Pass);
+        assert_eq!(
+            answers.check(1, 2, "142"),
+            Check::Fail {
+                expected: "281".to_string()
+            }
+        );
+        assert_eq!(answers.check(7, 1, "6440"), Check::Unknown);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        assert!(Answers::parse("1 one 142").is_err());
+        let err = Answers::parse(&format!("{INPUT}\n7 x 6440")).unwrap_err();
+        assert!(err.to_string().starts_with("line 6, column 3: "));
+    }
+}
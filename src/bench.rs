@@ -0,0 +1,229 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{days::Day, timing::Samples};
+
+pub const DEFAULT_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    // untimed runs before sampling, to warm up caches and the allocator
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            samples: 10,
+        }
+    }
+}
+
+// The timings of one day, per stage: "parse", "part1" and "part2".
+#[derive(Debug)]
+pub struct Bench {
+    pub day: u8,
+    pub stages: Vec<(&'static str, Samples)>,
+}
+
+pub fn run(day: &Day, input: &str, config: &Config) -> Result<Bench> {
+    let mut parse = Samples::default();
+    let mut parts = [Samples::default(), Samples::default()];
+    for run in 0..config.warmup + config.samples {
+        let solved = day.solve(input, &[1, 2])?;
+        for solved_part in &solved.parts {
+            if let Err(err) = &solved_part.answer {
+                anyhow::bail!("part {} failed: {err}", solved_part.part);
+            }
+        }
+        if run < config.warmup {
+            continue;
+        }
+        parse.push(solved.parse_time);
+        for (solved_part, samples) in solved.parts.iter().zip(&mut parts) {
+            samples.push(solved_part.elapsed);
+        }
+    }
+    let [part1, part2] = parts;
+    Ok(Bench {
+        day: day.day(),
+        stages: vec![("parse", parse), ("part1", part1), ("part2", part2)],
+    })
+}
+
+// The contents of a baseline file, with durations in nanoseconds:
+//
+// {"days": [{"day": 7, "stages": {"parse": {"median_ns": 1200, "mean_ns": ...}, ...}}, ...]}
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub days: Vec<DayReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: u8,
+    pub stages: BTreeMap<String, StageStats>,
+}
+
+// The statistics are missing for stages without samples.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StageStats {
+    pub median_ns: Option<u64>,
+    pub mean_ns: Option<u64>,
+    pub std_dev_ns: Option<u64>,
+    pub min_ns: Option<u64>,
+    pub max_ns: Option<u64>,
+    pub samples: usize,
+}
+
+impl Report {
+    pub fn new(benches: &[Bench]) -> Self {
+        let nanos = |d: Option<Duration>| d.map(|d| d.as_nanos() as u64);
+        let days = benches
+            .iter()
+            .map(|bench| DayReport {
+                day: bench.day,
+                stages: bench
+                    .stages
+                    .iter()
+                    .map(|(stage, samples)| {
+                        let stats = StageStats {
+                            median_ns: nanos(samples.median()),
+                            mean_ns: nanos(samples.mean()),
+                            std_dev_ns: nanos(samples.std_dev()),
+                            min_ns: nanos(samples.min()),
+                            max_ns: nanos(samples.max()),
+                            samples: samples.len(),
+                        };
+                        (stage.to_string(), stats)
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self { days }
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json).with_context(|| format!("unable to write baseline file: {path}"))
+    }
+}
+
+// The median of every (day, stage) in a file written by `Report::save`.
+#[derive(Debug, Default)]
+pub struct Baseline(BTreeMap<(u8, String), Duration>);
+
+impl Baseline {
+    pub fn load(path: &str) -> Result<Self> {
+        let input = std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read baseline file: {path}"))?;
+        Self::parse(&input).with_context(|| format!("invalid baseline file: {path}"))
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let report: Report = serde_json::from_str(input)?;
+        let medians = report
+            .days
+            .into_iter()
+            .flat_map(|day_report| {
+                let day = day_report.day;
+                day_report
+                    .stages
+                    .into_iter()
+                    .filter_map(move |(stage, stats)| {
+                        Some(((day, stage), Duration::from_nanos(stats.median_ns?)))
+                    })
+            })
+            .collect();
+        Ok(Self(medians))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+    // relative change of the median, e.g. 0.25 for 25% slower
+    pub change: f64,
+    pub regressed: bool,
+}
+
+// Compares the medians of every stage that is also in the baseline. A stage regressed when it got
+// slower by more than `threshold`, given as a fraction of the baseline.
+pub fn compare(baseline: &Baseline, benches: &[Bench], threshold: f64) -> Vec<Comparison> {
+    let mut comparisons = Vec::new();
+    for bench in benches {
+        for (stage, samples) in &bench.stages {
+            let key = (bench.day, stage.to_string());
+            let (Some(&before), Some(current)) = (baseline.0.get(&key), samples.median()) else {
+                continue;
+            };
+            let change = if before.is_zero() {
+                0.0
+            } else {
+                current.as_secs_f64() / before.as_secs_f64() - 1.0
+            };
+            comparisons.push(Comparison {
+                day: bench.day,
+                stage,
+                baseline: before,
+                current,
+                change,
+                regressed: change > threshold,
+            });
+        }
+    }
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bench(day: u8, parse_ms: &[u64], part1_ms: &[u64]) -> Bench {
+        let samples = |ms: &[u64]| ms.iter().copied().map(Duration::from_millis).collect();
+        Bench {
+            day,
+            stages: vec![("parse", samples(parse_ms)), ("part1", samples(part1_ms))],
+        }
+    }
+
+    #[test]
+    fn test_baseline_round_trip() -> Result<()> {
+        let benches = [bench(7, &[3, 1, 2], &[10, 12]), bench(8, &[], &[5])];
+        let json = serde_json::to_string_pretty(&Report::new(&benches))?;
+        let baseline = Baseline::parse(&json)?;
+        assert_eq!(
+            baseline.0,
+            BTreeMap::from([
+                ((7, "parse".to_string()), Duration::from_millis(2)),
+                ((7, "part1".to_string()), Duration::from_millis(10)),
+                ((8, "part1".to_string()), Duration::from_millis(5)),
+            ])
+        );
+        assert!(Baseline::parse("{}").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare() -> Result<()> {
+        let json = serde_json::to_string(&Report::new(&[bench(7, &[100], &[100])]))?;
+        let baseline = Baseline::parse(&json)?;
+        let comparisons = compare(
+            &baseline,
+            &[bench(7, &[105], &[150]), bench(9, &[1], &[1])],
+            0.1,
+        );
+        let flagged: Vec<_> = comparisons.iter().map(|c| (c.stage, c.regressed)).collect();
+        assert_eq!(flagged, vec![("parse", false), ("part1", true)]);
+        assert!((comparisons[1].change - 0.5).abs() < 1e-9);
+        Ok(())
+    }
+}
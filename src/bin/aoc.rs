@@ -0,0 +1,407 @@
+use std::{path::Path, process::ExitCode, time::Duration};
+
+use anyhow::{Context, Result};
+use aoc::{
+    answers::{self, Answers, Check},
+    bench,
+    days::{self, day23, Day},
+    report::{self, Format, Record},
+    scaffold, trace, Failure,
+};
+
+const USAGE: &str =
+    "usage: aoc run [all|DAY|FROM..TO|FROM..=TO] [1|2] [--check] [--answers answers_file_path]
+               [--format json|csv] [--trace SPEC] [--step POINTS]
+               [--strategy exhaustive|pruned|parallel]
+       aoc new DAY [--template template_path]
+       aoc bench [all|DAY|FROM..TO|FROM..=TO] [--warmup N] [--samples N] [--save baseline_file_path]
+                 [--baseline baseline_file_path] [--threshold PERCENT]
+                 [--strategy exhaustive|pruned|parallel]
+
+--strategy picks the longest path search of day 23, pruned by default";
+
+// The records of one day. When the input file couldn't be read, every record carries that error
+// and `input_missing` is set.
+struct Row {
+    day: &'static Day,
+    records: Vec<Record>,
+    input_missing: bool,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            failure.report(USAGE);
+            failure.exit_code()
+        }
+    }
+}
+
+fn run() -> Result<(), Failure> {
+    let usage = |msg: String| Failure::Usage(anyhow::format_err!(msg));
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => {
+            let mut positional = Vec::new();
+            let mut check = false;
+            let mut answers_path = answers::DEFAULT_PATH.to_string();
+            let mut format: Option<Format> = None;
+            let (mut trace_spec, mut step) = (None, None);
+            let mut strategy = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--check" => check = true,
+                    "--answers" => {
+                        answers_path = args
+                            .next()
+                            .ok_or_else(|| usage("--answers requires a value".to_string()))?;
+                        check = true;
+                    }
+                    "--format" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| usage("--format requires a value".to_string()))?;
+                        format = Some(value.parse().map_err(Failure::Usage)?);
+                    }
+                    "--trace" => {
+                        trace_spec = Some(
+                            args.next()
+                                .ok_or_else(|| usage("--trace requires a value".to_string()))?,
+                        );
+                    }
+                    "--step" => {
+                        step = Some(
+                            args.next()
+                                .ok_or_else(|| usage("--step requires a value".to_string()))?,
+                        );
+                    }
+                    "--strategy" => {
+                        let value = args
+                            .next()
+                            .ok_or_else(|| usage("--strategy requires a value".to_string()))?;
+                        strategy = Some(value.parse().map_err(Failure::Usage)?);
+                    }
+                    _ => positional.push(arg),
+                }
+            }
+            let mut positional = positional.into_iter();
+            let spec = positional.next().unwrap_or_else(|| "all".to_string());
+            let parts = match positional.next().as_deref() {
+                None => vec![1, 2],
+                Some("1") => vec![1],
+                Some("2") => vec![2],
+                Some(p) => return Err(usage(format!("unknown part: {p}"))),
+            };
+            if let Some(arg) = positional.next() {
+                return Err(usage(format!("unexpected argument: {arg}")));
+            }
+            if check && format.is_some() {
+                return Err(usage("--check can't be combined with --format".to_string()));
+            }
+
+            trace::Config::from_env(trace_spec.as_deref(), step.as_deref())
+                .and_then(trace::init)
+                .map_err(Failure::Usage)?;
+
+            let selected = days::select(&spec).map_err(Failure::Usage)?;
+            set_strategy(&selected, strategy).map_err(Failure::Usage)?;
+            let rows = run_all(&selected, &parts);
+            if check {
+                let answers = Answers::load(&answers_path).map_err(Failure::Io)?;
+                check_all(&answers, &rows).map_err(Failure::Check)
+            } else if let Some(format) = format {
+                let records: Vec<_> = rows.into_iter().flat_map(|row| row.records).collect();
+                report::print(format, &records);
+                Ok(())
+            } else {
+                print_table(&parts, &rows);
+                Ok(())
+            }
+        }
+        Some("new") => {
+            let mut day = None;
+            let mut template_path = scaffold::DEFAULT_TEMPLATE_PATH.to_string();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--template" => {
+                        template_path = args
+                            .next()
+                            .ok_or_else(|| usage("--template requires a value".to_string()))?;
+                    }
+                    _ if day.is_none() => {
+                        day = Some(
+                            arg.parse::<u8>()
+                                .map_err(|_| usage(format!("invalid day: {arg:?}")))?,
+                        );
+                    }
+                    _ => return Err(usage(format!("unexpected argument: {arg}"))),
+                }
+            }
+            let day = day.ok_or_else(|| usage("day is missing".to_string()))?;
+            let written = scaffold::new_day(Path::new("."), day, Path::new(&template_path))
+                .map_err(Failure::Io)?;
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Some("bench") => {
+            let mut spec = None;
+            let mut config = bench::Config::default();
+            let mut save_path = None;
+            let mut baseline_path = None;
+            let mut threshold = bench::DEFAULT_THRESHOLD;
+            let mut strategy = None;
+            while let Some(arg) = args.next() {
+                let mut value = |name: &str| {
+                    args.next()
+                        .ok_or_else(|| usage(format!("{name} requires a value")))
+                };
+                let number = |name: &str, value: String| {
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| usage(format!("invalid {name} value: {value}")))
+                };
+                // a non-negative percentage, ex: 2.5
+                let percent = |value: String| match value.parse::<f64>() {
+                    Ok(percent) if percent >= 0.0 && percent.is_finite() => Ok(percent / 100.0),
+                    _ => Err(usage(format!("invalid --threshold value: {value}"))),
+                };
+                match arg.as_str() {
+                    "--warmup" => config.warmup = number("--warmup", value("--warmup")?)?,
+                    "--samples" => {
+                        config.samples = number("--samples", value("--samples")?)?.max(1)
+                    }
+                    "--save" => save_path = Some(value("--save")?),
+                    "--baseline" => baseline_path = Some(value("--baseline")?),
+                    "--threshold" => threshold = percent(value("--threshold")?)?,
+                    "--strategy" => {
+                        strategy = Some(value("--strategy")?.parse().map_err(Failure::Usage)?)
+                    }
+                    _ if spec.is_none() => spec = Some(arg),
+                    _ => return Err(usage(format!("unexpected argument: {arg}"))),
+                }
+            }
+            let selected =
+                days::select(spec.as_deref().unwrap_or("all")).map_err(Failure::Usage)?;
+            set_strategy(&selected, strategy).map_err(Failure::Usage)?;
+            // load the baseline up front, so that a typo doesn't waste a whole benchmark run
+            let baseline = baseline_path
+                .map(|path| bench::Baseline::load(&path))
+                .transpose()
+                .map_err(Failure::Io)?;
+
+            let benches = bench_all(&selected, &config);
+            if let Some(path) = save_path {
+                bench::Report::new(&benches)
+                    .save(&path)
+                    .map_err(Failure::Io)?;
+                println!("saved baseline to {path}");
+            }
+            match baseline {
+                Some(baseline) => {
+                    let comparisons = bench::compare(&baseline, &benches, threshold);
+                    print_comparisons(&comparisons).map_err(Failure::Check)
+                }
+                None => Ok(()),
+            }
+        }
+        Some(cmd) => Err(usage(format!("unknown cmd: {cmd}"))),
+        None => Err(usage("cmd is missing".to_string())),
+    }
+}
+
+// Rejects a strategy that no selected day would use, rather than silently ignoring it.
+fn set_strategy(selected: &[&'static Day], strategy: Option<day23::Strategy>) -> Result<()> {
+    let Some(strategy) = strategy else {
+        return Ok(());
+    };
+    anyhow::ensure!(
+        selected.iter().any(|day| day.day() == 23),
+        "--strategy only applies to day 23"
+    );
+    day23::set_strategy(strategy)
+}
+
+fn run_all(selected: &[&'static Day], parts: &[u8]) -> Vec<Row> {
+    selected
+        .iter()
+        .map(|day| {
+            let input_path = day.input_path();
+            let input = std::fs::read_to_string(&input_path)
+                .with_context(|| format!("unable to read input file: {input_path}"));
+            let input_missing = input.is_err();
+            let solved = input.and_then(|input| day.solve(&input, parts));
+            let record = |part, answer, elapsed| Record {
+                day: day.day(),
+                part,
+                answer,
+                elapsed,
+                input_path: input_path.clone(),
+            };
+            let records = match solved {
+                Ok(solved) => solved
+                    .parts
+                    .into_iter()
+                    .map(|solved_part| {
+                        record(
+                            solved_part.part,
+                            solved_part.answer,
+                            Some(solved_part.elapsed),
+                        )
+                    })
+                    .collect(),
+                Err(err) => parts
+                    .iter()
+                    .map(|&part| record(part, Err(anyhow::format_err!("{err:#}")), None))
+                    .collect(),
+            };
+            Row {
+                day: *day,
+                records,
+                input_missing,
+            }
+        })
+        .collect()
+}
+
+// Days that fail to solve are reported and left out of the results.
+fn bench_all(selected: &[&'static Day], config: &bench::Config) -> Vec<bench::Bench> {
+    let fmt = |d: Option<Duration>| d.map_or("-".to_string(), |d| format!("{d:.2?}"));
+    println!("day  stage  median ± std dev (min .. max)");
+    let mut benches = Vec::new();
+    for day in selected {
+        let bench = std::fs::read_to_string(day.input_path())
+            .with_context(|| format!("unable to read input file: {}", day.input_path()))
+            .and_then(|input| bench::run(*day, &input, config));
+        match bench {
+            Ok(bench) => {
+                for (stage, samples) in &bench.stages {
+                    println!(
+                        "{:>3}  {stage:<5}  {} ± {} ({} .. {})",
+                        bench.day,
+                        fmt(samples.median()),
+                        fmt(samples.std_dev()),
+                        fmt(samples.min()),
+                        fmt(samples.max()),
+                    );
+                }
+                benches.push(bench);
+            }
+            Err(err) => println!("{:>3}  error: {err}", day.day()),
+        }
+    }
+    benches
+}
+
+fn print_comparisons(comparisons: &[bench::Comparison]) -> Result<()> {
+    println!("\nday  stage  baseline -> current");
+    let mut regressions = 0;
+    for c in comparisons {
+        let flag = if c.regressed {
+            regressions += 1;
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "{:>3}  {:<5}  {:.2?} -> {:.2?} ({:+.1}%){flag}",
+            c.day,
+            c.stage,
+            c.baseline,
+            c.current,
+            c.change * 100.0
+        );
+    }
+    anyhow::ensure!(regressions == 0, "{regressions} stages regressed");
+    Ok(())
+}
+
+fn print_table(parts: &[u8], rows: &[Row]) {
+    let headers: Vec<_> = std::iter::once("title".to_string())
+        .chain(parts.iter().map(|p| format!("part {p}")))
+        .collect();
+    let rows: Vec<_> = rows
+        .iter()
+        .map(|Row { day, records, .. }| {
+            let answers = records.iter().map(|record| match &record.answer {
+                Ok(answer) => answer.clone(),
+                Err(err) => format!("error: {err}"),
+            });
+            let cells: Vec<_> = std::iter::once(day.title().to_string())
+                .chain(answers)
+                .collect();
+            (day.day().to_string(), cells)
+        })
+        .collect();
+    let widths: Vec<_> = headers
+        .iter()
+        .enumerate()
+        .map(|(idx, h)| {
+            rows.iter()
+                .map(|(_, cells)| cells[idx].len())
+                .chain([h.len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let print_row = |day: &str, cells: &[String]| {
+        let mut line = format!("{day:>3}");
+        for (cell, width) in cells.iter().zip(&widths) {
+            line.push_str(&format!("  {cell:<width$}"));
+        }
+        println!("{}", line.trim_end());
+    };
+    print_row("day", &headers);
+    for (day, cells) in &rows {
+        print_row(day, cells);
+    }
+}
+
+// Prints a diff-style report: matching answers are prefixed with two spaces, mismatches show the
+// expected answer with `-` and the actual one with `+`, `?` marks answers without an expectation
+// as well as days skipped for a missing input file, and `!` marks days that failed to produce an
+// answer. Only mismatches and failures fail the check.
+fn check_all(expected: &Answers, rows: &[Row]) -> Result<()> {
+    let (mut passed, mut failed, mut unknown, mut skipped) = (0, 0, 0, 0);
+    for row in rows {
+        for Record { part, answer, .. } in &row.records {
+            let label = format!("day {:>2} part {part}", row.day.day());
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(err) if row.input_missing => {
+                    skipped += 1;
+                    println!("? {label}: skipped, {err}");
+                    continue;
+                }
+                Err(err) => {
+                    failed += 1;
+                    println!("! {label}: error: {err}");
+                    continue;
+                }
+            };
+            match expected.check(row.day.day(), *part, answer) {
+                Check::Pass => {
+                    passed += 1;
+                    println!("  {label}: {answer}");
+                }
+                Check::Fail { expected } => {
+                    failed += 1;
+                    println!("- {label}: {expected}");
+                    println!("+ {label}: {answer}");
+                }
+                Check::Unknown => {
+                    unknown += 1;
+                    println!("? {label}: {answer}");
+                }
+            }
+        }
+    }
+    println!(
+        "\n{passed} passed, {failed} failed, {unknown} without expected answer, {skipped} skipped"
+    );
+    anyhow::ensure!(failed == 0, "{failed} answers failed the check");
+    Ok(())
+}
@@ -1,64 +1,529 @@
-use core::num::NonZeroUsize;
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::{BitAnd, BitOr, BitXor, Sub},
+};
 
+use anyhow::Result;
+
+// A set of small non-negative integers, stored as one bit per element in 64-bit words.
+//
+// The storage decides how wide the set can get: `BitSet<[u64; N]>` holds up to N * 64 elements
+// and is `Copy`, while `HeapBitSet` grows as needed.
+//
+// Sets are equal when they hold the same elements, regardless of capacity. They are ordered as if
+// the bits were one big unsigned integer.
 #[derive(Debug, Clone, Copy)]
-pub struct BitSet(u64);
+pub struct BitSet<W = [u64; 1]>(W);
+
+pub type HeapBitSet = BitSet<Vec<u64>>;
+
+// Backing storage for a `BitSet`.
+pub trait Words: AsRef<[u64]> + AsMut<[u64]> + Clone {
+    fn empty() -> Self;
+    // Makes room for at least `num_words` words, returning false if the storage can't grow.
+    fn grow(&mut self, num_words: usize) -> bool;
+}
+
+impl<const N: usize> Words for [u64; N] {
+    fn empty() -> Self {
+        [0; N]
+    }
+
+    fn grow(&mut self, num_words: usize) -> bool {
+        num_words <= N
+    }
+}
 
-impl BitSet {
+impl Words for Vec<u64> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+
+    fn grow(&mut self, num_words: usize) -> bool {
+        if self.len() < num_words {
+            self.resize(num_words, 0);
+        }
+        true
+    }
+}
+
+impl<W: Words> BitSet<W> {
     pub fn new() -> Self {
-        Self(0)
+        Self(W::empty())
+    }
+
+    // The number of elements that fit without growing.
+    pub fn capacity(&self) -> usize {
+        self.0.as_ref().len() * 64
     }
 
+    // Panics if `off` doesn't fit in fixed-size storage; see `try_set`.
     pub fn set(&mut self, off: usize) {
-        self.0 |= 1 << off
+        if let Err(err) = self.try_set(off) {
+            panic!("{err}");
+        }
+    }
+
+    pub fn try_set(&mut self, off: usize) -> Result<()> {
+        let (word, bit) = (off / 64, off % 64);
+        anyhow::ensure!(
+            self.0.grow(word + 1),
+            "index {off} is out of bounds for a bit set of capacity {}",
+            self.capacity()
+        );
+        self.0.as_mut()[word] |= 1 << bit;
+        Ok(())
+    }
+
+    // Like `collect`, but fails instead of panicking when an element doesn't fit.
+    pub fn try_from_iter(iter: impl IntoIterator<Item = usize>) -> Result<Self> {
+        let mut set = Self::new();
+        for idx in iter {
+            set.try_set(idx)?;
+        }
+        Ok(set)
+    }
+
+    // Returns whether `off` was in the set.
+    pub fn remove(&mut self, off: usize) -> bool {
+        let (word, bit) = (off / 64, off % 64);
+        match self.0.as_mut().get_mut(word) {
+            Some(bits) => {
+                let was_set = *bits & (1 << bit) != 0;
+                *bits &= !(1 << bit);
+                was_set
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, off: usize) -> bool {
+        let (word, bit) = (off / 64, off % 64);
+        self.0
+            .as_ref()
+            .get(word)
+            .is_some_and(|bits| bits & (1 << bit) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0
+            .as_ref()
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.as_ref().iter().all(|w| *w == 0)
     }
 
-    pub fn difference(&self, Self(other_bits): Self) -> Self {
-        Self(self.0 & !other_bits)
+    // The smallest element.
+    pub fn first(&self) -> Option<usize> {
+        let words = self.0.as_ref();
+        let word = words.iter().position(|w| *w != 0)?;
+        Some(word * 64 + words[word].trailing_zeros() as usize)
+    }
+
+    // The largest element.
+    pub fn last(&self) -> Option<usize> {
+        let words = self.0.as_ref();
+        let word = words.iter().rposition(|w| *w != 0)?;
+        Some(word * 64 + 63 - words[word].leading_zeros() as usize)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        let other_words = other.0.as_ref();
+        self.0
+            .as_ref()
+            .iter()
+            .enumerate()
+            .all(|(i, bits)| bits & !other_words.get(i).unwrap_or(&0) == 0)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn iter(&self) -> BitSetIter<&[u64]> {
+        BitSetIter::new(self.0.as_ref())
+    }
+
+    // Applies `op` word by word, treating words past the end of the shorter set as zeros.
+    fn combine(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        let mut result = self.clone();
+        let other_words = other.0.as_ref();
+        result.0.grow(other_words.len());
+        for (i, bits) in result.0.as_mut().iter_mut().enumerate() {
+            *bits = op(*bits, other_words.get(i).copied().unwrap_or(0));
+        }
+        result
+    }
+
+    // The words without trailing empty ones, so that capacity doesn't affect comparisons.
+    fn trimmed(&self) -> &[u64] {
+        let words = self.0.as_ref();
+        let len = words.iter().rposition(|w| *w != 0).map_or(0, |i| i + 1);
+        &words[..len]
+    }
+}
+
+impl<W: Words> Default for BitSet<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Words> PartialEq for BitSet<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl<W: Words> Eq for BitSet<W> {}
+
+impl<W: Words> Hash for BitSet<W> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state)
     }
 }
 
-impl std::iter::IntoIterator for BitSet {
-    type Item = NonZeroUsize;
-    type IntoIter = BitSetIter;
+impl<W: Words> PartialOrd for BitSet<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Words> Ord for BitSet<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.trimmed(), other.trimmed());
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+}
+
+macro_rules! impl_set_op {
+    ($($trait:ident::$method:ident => $set_method:ident),*) => {$(
+        impl<W: Words> $trait for BitSet<W> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                self.$set_method(&rhs)
+            }
+        }
+
+        impl<W: Words> $trait for &BitSet<W> {
+            type Output = BitSet<W>;
+
+            fn $method(self, rhs: Self) -> BitSet<W> {
+                self.$set_method(rhs)
+            }
+        }
+    )*};
+}
+
+impl_set_op!(
+    BitOr::bitor => union,
+    BitAnd::bitand => intersection,
+    BitXor::bitxor => symmetric_difference,
+    Sub::sub => difference
+);
+
+impl<W: Words> std::iter::IntoIterator for BitSet<W> {
+    type Item = usize;
+    type IntoIter = BitSetIter<W>;
 
     fn into_iter(self) -> Self::IntoIter {
-        BitSetIter(self.0)
+        BitSetIter::new(self.0)
     }
 }
 
-impl std::iter::FromIterator<usize> for BitSet {
+impl<'a, W: Words> std::iter::IntoIterator for &'a BitSet<W> {
+    type Item = usize;
+    type IntoIter = BitSetIter<&'a [u64]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<W: Words> std::iter::FromIterator<usize> for BitSet<W> {
     fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
-        let bits = iter
-            .into_iter()
-            .fold(0_usize, |bits, idx| bits | (1 << idx));
-        Self(bits as u64)
+        let mut set = Self::new();
+        for idx in iter {
+            set.set(idx);
+        }
+        set
     }
 }
 
+// Yields the elements of a set in increasing order, or decreasing order from the back.
 #[derive(Debug)]
-pub struct BitSetIter(u64);
+pub struct BitSetIter<W> {
+    words: W,
+    // indices of the words that `front_bits` and `back_bits` came from; once they meet, the
+    // remaining bits of that word are in `front_bits`
+    front: usize,
+    back: usize,
+    front_bits: u64,
+    back_bits: u64,
+    remaining: usize,
+}
+
+impl<W: AsRef<[u64]>> BitSetIter<W> {
+    fn new(words: W) -> Self {
+        let slice = words.as_ref();
+        let remaining = slice.iter().map(|w| w.count_ones() as usize).sum();
+        let back = slice.len().saturating_sub(1);
+        Self {
+            front: 0,
+            back,
+            front_bits: slice.first().copied().unwrap_or(0),
+            back_bits: slice.get(back).copied().unwrap_or(0),
+            remaining,
+            words,
+        }
+    }
+}
 
-impl std::iter::Iterator for BitSetIter {
-    type Item = NonZeroUsize;
+impl<W: AsRef<[u64]>> std::iter::Iterator for BitSetIter<W> {
+    type Item = usize;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        match self.0 {
-            0 => None,
-            bits => {
-                let idx = bits.trailing_zeros() as usize;
-                self.0 ^= 1 << idx;
-                NonZeroUsize::new(idx)
-            }
+        if self.remaining == 0 {
+            return None;
+        }
+        while self.front_bits == 0 {
+            self.front += 1;
+            self.front_bits = if self.front == self.back {
+                self.back_bits
+            } else {
+                self.words.as_ref()[self.front]
+            };
         }
+        let bit = self.front_bits.trailing_zeros() as usize;
+        self.front_bits ^= 1 << bit;
+        self.remaining -= 1;
+        Some(self.front * 64 + bit)
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let n = self.0.count_ones() as usize;
-        (n, Some(n))
+        (self.remaining, Some(self.remaining))
     }
 }
 
-impl std::iter::ExactSizeIterator for BitSetIter {}
-impl std::iter::FusedIterator for BitSetIter {}
+impl<W: AsRef<[u64]>> std::iter::DoubleEndedIterator for BitSetIter<W> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (word, bits) = loop {
+            if self.back == self.front {
+                break (self.front, &mut self.front_bits);
+            }
+            if self.back_bits != 0 {
+                break (self.back, &mut self.back_bits);
+            }
+            self.back -= 1;
+            self.back_bits = self.words.as_ref()[self.back];
+        };
+        let bit = 63 - bits.leading_zeros() as usize;
+        *bits ^= 1 << bit;
+        self.remaining -= 1;
+        Some(word * 64 + bit)
+    }
+}
+
+impl<W: AsRef<[u64]>> std::iter::ExactSizeIterator for BitSetIter<W> {}
+impl<W: AsRef<[u64]>> std::iter::FusedIterator for BitSetIter<W> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_wide_sets() {
+        let fixed: BitSet<[u64; 3]> = [1, 63, 64, 150].into_iter().collect();
+        assert_eq!(fixed.capacity(), 192);
+        let elements: Vec<_> = fixed.into_iter().collect();
+        assert_eq!(elements, vec![1, 63, 64, 150]);
+
+        let mut heap = HeapBitSet::new();
+        assert_eq!(heap.capacity(), 0);
+        heap.set(1000);
+        heap.set(64);
+        assert_eq!(heap.capacity(), 1024);
+        let elements: Vec<_> = heap.iter().collect();
+        assert_eq!(elements, vec![64, 1000]);
+
+        let other: HeapBitSet = [64].into_iter().collect();
+        let diff = heap.difference(&other);
+        assert_eq!(diff.iter().len(), 1);
+        assert_eq!(other.difference(&heap).iter().len(), 0);
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a: BitSet = [1, 2, 3].into_iter().collect();
+        let b: BitSet = [3, 4].into_iter().collect();
+        let elements = |s: BitSet| s.into_iter().collect::<Vec<_>>();
+        assert_eq!(elements(a | b), vec![1, 2, 3, 4]);
+        assert_eq!(elements(a & b), vec![3]);
+        assert_eq!(elements(a - b), vec![1, 2]);
+        assert_eq!(elements(a ^ b), vec![1, 2, 4]);
+        assert!((a & b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!((a | b).is_superset(&b));
+        assert_eq!((a.len(), a.first(), a.last()), (3, Some(1), Some(3)));
+        assert!((a - a).is_empty());
+        assert_eq!(BitSet::<[u64; 1]>::new().first(), None);
+
+        let mut c = a;
+        assert!(c.contains(2));
+        assert!(c.remove(2));
+        assert!(!c.remove(2));
+        assert!(!c.contains(2));
+        assert!(!c.contains(1000));
+        assert!(c < a);
+    }
+
+    #[test]
+    fn test_heap_equality() {
+        use std::collections::HashSet;
+
+        let mut a: HeapBitSet = [5, 200].into_iter().collect();
+        let b: HeapBitSet = [5].into_iter().collect();
+        assert_ne!(a, b);
+        assert!(b < a);
+        a.remove(200);
+        // a keeps its extra capacity, but holds the same elements
+        assert!(a.capacity() > b.capacity());
+        assert_eq!(a, b);
+        assert_eq!(HashSet::from([a.clone(), b.clone()]).len(), 1);
+
+        let wide: HeapBitSet = [70].into_iter().collect();
+        assert_eq!((&b | &wide).last(), Some(70));
+        assert_eq!(&wide & &b, HeapBitSet::new());
+        assert_eq!((&wide ^ &b).len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_fixed_overflow() {
+        let mut set = BitSet::<[u64; 2]>::new();
+        set.set(128);
+    }
+
+    #[test]
+    fn test_checked_construction() {
+        let set = BitSet::<[u64; 1]>::try_from_iter([0, 63]).unwrap();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 63]);
+        assert!(BitSet::<[u64; 1]>::try_from_iter([0, 64]).is_err());
+        assert!(HeapBitSet::try_from_iter([0, 64]).is_ok());
+        let mut set = BitSet::<[u64; 1]>::new();
+        assert!(set.try_set(100).is_err());
+        assert!(set.is_empty());
+    }
+
+    // xorshift, so that the property tests are reproducible
+    fn rng(mut seed: u64) -> impl FnMut(usize) -> usize {
+        move |bound| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        }
+    }
+
+    // Applies the same random operations to a bit set and a BTreeSet, checking that they agree.
+    fn check_against_model<W: Words>(max_elem: usize, seed: u64) {
+        let mut next = rng(seed);
+        let mut set = BitSet::<W>::new();
+        let mut model = BTreeSet::new();
+        for _ in 0..500 {
+            let elem = next(max_elem);
+            match next(4) {
+                0 | 1 => {
+                    set.set(elem);
+                    model.insert(elem);
+                }
+                2 => assert_eq!(set.remove(elem), model.remove(&elem)),
+                _ => {
+                    let other_model: BTreeSet<_> = (0..next(8)).map(|_| next(max_elem)).collect();
+                    let other: BitSet<W> = other_model.iter().copied().collect();
+                    assert_eq!(set.is_subset(&other), model.is_subset(&other_model));
+                    (set, model) = match next(4) {
+                        0 => (&set | &other, model.union(&other_model).copied().collect()),
+                        1 => (
+                            &set & &other,
+                            model.intersection(&other_model).copied().collect(),
+                        ),
+                        2 => (
+                            &set - &other,
+                            model.difference(&other_model).copied().collect(),
+                        ),
+                        _ => (
+                            &set ^ &other,
+                            model.symmetric_difference(&other_model).copied().collect(),
+                        ),
+                    };
+                }
+            }
+
+            assert_eq!(set.len(), model.len());
+            assert_eq!(set.is_empty(), model.is_empty());
+            assert_eq!(set.contains(elem), model.contains(&elem));
+            assert_eq!(set.first(), model.first().copied());
+            assert_eq!(set.last(), model.last().copied());
+            assert!(set.iter().eq(model.iter().copied()));
+            assert!(set.iter().rev().eq(model.iter().rev().copied()));
+
+            // alternate between both ends
+            let mut iter = set.iter();
+            let mut model_iter = model.iter().copied();
+            loop {
+                assert_eq!(iter.len(), model_iter.len());
+                let (a, b) = if next(2) == 0 {
+                    (iter.next(), model_iter.next())
+                } else {
+                    (iter.next_back(), model_iter.next_back())
+                };
+                assert_eq!(a, b);
+                if a.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_btree_set() {
+        for seed in 1..20 {
+            check_against_model::<[u64; 1]>(64, seed);
+            check_against_model::<[u64; 3]>(192, seed);
+            check_against_model::<Vec<u64>>(300, seed);
+        }
+    }
+}
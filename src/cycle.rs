@@ -0,0 +1,167 @@
+use std::{collections::HashMap, hash::Hash};
+
+// The shape of the sequence x0, f(x0), f(f(x0)), ... once it starts repeating: `mu` is the index
+// of the first element of the cycle and `lambda` is the cycle length.
+//
+//                           mu
+// 0 1 2 3 4 5 6 7 8 9 0 1 |2| 3 4 5 6 7 8 9 ...
+// a b c a a a b b c a a a |b| b c a a a b b ...
+//
+// e.g. for mu = 3 and lambda = 6, the element at index 12 is the same as the one at
+// mu + (12 - mu) % lambda = 6.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub mu: usize,
+    pub lambda: usize,
+}
+
+impl Cycle {
+    // Maps any index of the sequence to the smallest index holding the same element.
+    pub fn reduce_index(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+}
+
+// See: Brent's algorithm
+// (https://en.m.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm)
+pub fn brent<T: Clone + Eq>(x0: &T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // main phase: search successive powers of two
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(x0);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = f(&hare);
+        lambda += 1;
+    }
+
+    // Find the position of the first repetition of length λ
+    tortoise = x0.clone();
+    hare = x0.clone();
+    for _ in 0..lambda {
+        hare = f(&hare);
+    }
+
+    // The distance between the hare and tortoise is now λ.
+
+    // Next, the hare and tortoise move at same speed until they agree
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        mu += 1;
+    }
+    Cycle { mu, lambda }
+}
+
+// See: Floyd's tortoise and hare
+// (https://en.m.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare)
+pub fn floyd<T: Clone + Eq>(x0: &T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // the hare moves twice as fast, so they meet at a multiple of λ
+    let mut tortoise = f(x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    // walking from x0 and the meeting point at the same speed, they agree at μ
+    let mut mu = 0;
+    tortoise = x0.clone();
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        lambda += 1;
+    }
+    Cycle { mu, lambda }
+}
+
+// Remembers every state it sees, so the cycle is found in exactly mu + lambda applications of `f`.
+// Also returns the states in order, which covers every index up to and including the first cycle.
+pub fn hashed<T: Clone + Hash + Eq>(x0: &T, mut f: impl FnMut(&T) -> T) -> (Cycle, Vec<T>) {
+    let mut seen = HashMap::new();
+    let mut history = vec![x0.clone()];
+    seen.insert(x0.clone(), 0);
+    loop {
+        let next = f(history.last().expect("history starts non-empty"));
+        if let Some(&mu) = seen.get(&next) {
+            let lambda = history.len() - mu;
+            return (Cycle { mu, lambda }, history);
+        }
+        seen.insert(next.clone(), history.len());
+        history.push(next);
+    }
+}
+
+// The state after applying `f` to `x0` `n` times, without doing all n applications.
+pub fn nth_state<T: Clone + Eq>(x0: &T, mut f: impl FnMut(&T) -> T, n: usize) -> T {
+    let cycle = brent(x0, &mut f);
+    let mut state = x0.clone();
+    for _ in 0..cycle.reduce_index(n) {
+        state = f(&state);
+    }
+    state
+}
+
+// Same as `nth_state`, but looks the state up instead of replaying the sequence.
+pub fn nth_state_hashed<T: Clone + Hash + Eq>(x0: &T, f: impl FnMut(&T) -> T, n: usize) -> T {
+    let (cycle, mut history) = hashed(x0, f);
+    history.swap_remove(cycle.reduce_index(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 ...
+    fn step(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        let expected = Cycle { mu: 3, lambda: 4 };
+        assert_eq!(brent(&0, step), expected);
+        assert_eq!(floyd(&0, step), expected);
+        assert_eq!(hashed(&0, step).0, expected);
+        assert_eq!(hashed(&0, step).1, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        // cycle starting at the first element
+        let expected = Cycle { mu: 0, lambda: 4 };
+        assert_eq!(brent(&3, step), expected);
+        assert_eq!(floyd(&3, step), expected);
+        assert_eq!(hashed(&3, step).0, expected);
+    }
+
+    #[test]
+    fn test_nth_state() {
+        for n in 0..20 {
+            let expected = (0..n).fold(0, |x, _| step(&x));
+            assert_eq!(nth_state(&0, step, n), expected, "n: {n}");
+            assert_eq!(nth_state_hashed(&0, step, n), expected, "n: {n}");
+        }
+        // 1_000_000_000 - 3 is a multiple of 4 plus 1
+        assert_eq!(nth_state_hashed(&0, step, 1_000_000_000), 4);
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+
+use crate::solution::DynSolution;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+
+pub type Day = dyn DynSolution;
+
+pub const DAYS: &[&Day] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+];
+
+pub fn get(day: u8) -> Result<&'static Day> {
+    DAYS.iter()
+        .find(|d| d.day() == day)
+        .copied()
+        .ok_or_else(|| anyhow::format_err!("day {day} is not registered"))
+}
+
+// Accepts a single day ("7"), "all", or a range of days ("10..15", "10..=15").
+pub fn select(spec: &str) -> Result<Vec<&'static Day>> {
+    let parse_day = |s: &str| {
+        s.parse::<u8>()
+            .map_err(|_| anyhow::format_err!("invalid day: {s:?}"))
+    };
+    let range = match spec {
+        "all" => return Ok(DAYS.to_vec()),
+        s => match s.split_once("..") {
+            Some((from, to)) => match to.strip_prefix('=') {
+                Some(to) => parse_day(from)?..=parse_day(to)?,
+                None => parse_day(from)?..=parse_day(to)?.saturating_sub(1),
+            },
+            None => {
+                let day = parse_day(s)?;
+                day..=day
+            }
+        },
+    };
+    let selected: Vec<_> = DAYS
+        .iter()
+        .copied()
+        .filter(|d| range.contains(&d.day()))
+        .collect();
+    anyhow::ensure!(!selected.is_empty(), "no registered days match {spec:?}");
+    Ok(selected)
+}
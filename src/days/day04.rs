@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, newline, space1},
+    combinator::{cut, map, map_res, not, opt},
+    error::{context, VerboseError},
+    multi::separated_list1,
+    sequence::{delimited, terminated, tuple},
+    IResult,
+};
+
+use crate::{must_parse, parse::line_break, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    type Input<'i> = Vec<Card>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>> {
+        let parser = terminated(
+            separated_list1(line_break, cut(context("card", Card::parse))),
+            opt(newline),
+        );
+        must_parse(parser, input)
+    }
+
+    fn part_one(&self, cards: &Vec<Card>) -> Result<usize> {
+        Ok(cards.iter().map(|g| g.points()).sum())
+    }
+
+    fn part_two(&self, cards: &Vec<Card>) -> Result<usize> {
+        let mut cards = cards.clone();
+        play_game(&mut cards);
+        Ok(cards.into_iter().map(|c| c.num_copies).sum())
+    }
+}
+
+fn play_game(cards: &mut Vec<Card>) {
+    for idx in 0..cards.len() {
+        let card = &cards[idx];
+        let num_copies_to_add = card.num_copies;
+        let dup_start_idx = idx + 1;
+        let dup_end_idx = dup_start_idx + card.num_winning();
+        for c in &mut cards[dup_start_idx..dup_end_idx] {
+            c.num_copies += num_copies_to_add;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    num_copies: usize,
+    winning_numbers: HashSet<usize>,
+    card_numbers: HashSet<usize>,
+}
+
+impl Card {
+    fn parse(line: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        fn number(input: &str) -> IResult<&str, usize, VerboseError<&str>> {
+            map_res(digit1, |num_str: &str| num_str.parse::<usize>())(input)
+        }
+
+        // the space before `|` doesn't separate another winning number
+        let gap = || terminated(space1, not(tag("|")));
+        let numbers = || separated_list1(gap(), cut(number));
+
+        // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        // once a line starts like a card, errors point into it rather than at its start
+        let parser = tuple((
+            tag("Card"),
+            cut(tuple((
+                space1,
+                context("card number", number),
+                tag(":"),
+                space1,
+                context("winning numbers", numbers()),
+                delimited(space1, tag("|"), space1),
+                context("card numbers", numbers()),
+            ))),
+        ));
+        map(
+            parser,
+            |(_, (_, _card_no, _, _, winning_numbers, _, card_numbers))| Self {
+                num_copies: 1,
+                winning_numbers: winning_numbers.into_iter().collect(),
+                card_numbers: card_numbers.into_iter().collect(),
+            },
+        )(line)
+    }
+
+    fn num_winning(&self) -> usize {
+        self.winning_numbers
+            .intersection(&self.card_numbers)
+            .count()
+    }
+
+    fn points(&self) -> usize {
+        match self.num_winning() {
+            0 => 0,
+            n => 2_usize.pow(n as u32 - 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day04.solve_part_one(INPUT)?, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("16 61 |", "x6 61 |");
+        let err = Day04.parse(&input).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "line 2, column 18: expected a number while parsing winning numbers while parsing card\n"
+        ));
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day04.solve_part_two(INPUT)?, 30);
+        Ok(())
+    }
+}
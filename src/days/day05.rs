@@ -0,0 +1,200 @@
+use std::fmt::Formatter;
+
+use anyhow::{Context, Result};
+use nom::{
+    bytes::complete::{is_not, tag, take_while1},
+    character::complete::{char, multispace1, newline},
+    combinator::{cut, map, map_res, opt},
+    error::{context, VerboseError},
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::{
+    interval::{IntervalSet, RangeMap},
+    must_parse,
+    parse::line_break,
+    Solution,
+};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    type Input<'i> = Almanac;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Almanac> {
+        Almanac::parse(input)
+    }
+
+    fn part_one(&self, alm: &Almanac) -> Result<usize> {
+        Ok(alm
+            .seeds
+            .iter()
+            .map(|seed| alm.location(*seed))
+            .min()
+            .expect("could not find min"))
+    }
+
+    fn part_two(&self, alm: &Almanac) -> Result<usize> {
+        alm.min_location_for(alm.seed_ranges())
+            .context("minimum location not found")
+    }
+}
+
+type Seed = usize;
+type Loc = usize;
+
+#[allow(unused)]
+#[derive(Debug)]
+struct Mapping {
+    name: String,
+    ranges: RangeMap<usize>,
+}
+
+impl std::fmt::Display for Mapping {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{name}{ranges:?}",
+            name = self.name,
+            ranges = self.ranges
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<Seed>,
+    mappings: Vec<Mapping>,
+}
+
+impl Almanac {
+    fn parse(input: &str) -> Result<Self> {
+        fn number(input: &str) -> IResult<&str, usize, VerboseError<&str>> {
+            map_res(
+                take_while1(|ch: char| ch.is_ascii_digit()),
+                |num_str: &str| num_str.parse::<usize>(),
+            )(input)
+        }
+        let parse_seeds = context("seeds", separated_list1(char(' '), cut(number)));
+        let parse_range = context(
+            "range",
+            tuple((number, char(' '), number, char(' '), number)),
+        );
+        let parse_ranges = separated_list1(line_break, cut(parse_range));
+        let parse_mapping = map_res(
+            tuple((
+                context("mapping", is_not(" ")),
+                tag(" map:\n"),
+                parse_ranges,
+            )),
+            |(name, _, ranges)| -> Result<Mapping> {
+                let mut mapping = Mapping {
+                    name: name.to_string(),
+                    ranges: RangeMap::new(),
+                };
+                for (dest_start, _, src_start, _, len) in ranges {
+                    mapping
+                        .ranges
+                        .insert(src_start..src_start + len, dest_start)?;
+                }
+                Ok(mapping)
+            },
+        );
+        let parse_mappings = separated_list1(preceded(newline, line_break), cut(parse_mapping));
+        let parser = map(
+            tuple((
+                tag("seeds: "),
+                parse_seeds,
+                multispace1,
+                parse_mappings,
+                opt(char('\n')),
+            )),
+            |(_, seeds, _, mappings, _)| Self { seeds, mappings },
+        );
+        must_parse(parser, input)
+    }
+
+    fn location(&self, seed: Seed) -> Loc {
+        self.mappings.iter().fold(seed, |acc, m| m.ranges.get(acc))
+    }
+
+    fn seed_ranges(&self) -> IntervalSet<Seed> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|sl| sl[0]..sl[0] + sl[1])
+            .collect()
+    }
+
+    fn min_location_for(&self, seeds: IntervalSet<Seed>) -> Option<Loc> {
+        let locs = self
+            .mappings
+            .iter()
+            .fold(seeds, |inputs, m| m.ranges.map_set(&inputs));
+        locs.first()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        Almanac::parse(INPUT).map(|_| ())
+    }
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        let alm = Day05.parse(INPUT)?;
+        assert_eq!(Day05.part_one(&alm)?, 35);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let alm = Day05.parse(INPUT)?;
+        assert_eq!(Day05.part_two(&alm)?, 46);
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cycle::{self, Cycle},
+    math, must_parse,
+    parse::line_break,
+    Solution,
+};
+use anyhow::{Context, Result};
+use nom::{
+    bytes::complete::{is_not, tag},
+    character::complete::{self, alphanumeric1, newline},
+    combinator::{cut, map, opt},
+    error::context,
+    multi::{count, separated_list1},
+    sequence::{delimited, separated_pair, terminated},
+};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+
+    type Input<'i> = Map<'i>;
+    type PartOne = u32;
+    type PartTwo = usize;
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Map<'i>> {
+        Map::parse(input)
+    }
+
+    fn part_one(&self, map: &Map) -> Result<u32> {
+        let mut count = 0;
+        let mut nodes = map.lookup("AAA");
+        for ins in map.ins_iter() {
+            count += 1;
+            let next = match ins {
+                Ins::Left => nodes.0,
+                Ins::Right => nodes.1,
+            };
+            if next == "ZZZ" {
+                break;
+            }
+            nodes = map.lookup(next);
+        }
+        Ok(count)
+    }
+
+    fn part_two(&self, map: &Map) -> Result<usize> {
+        let starting_nodes = map.network.keys().filter(|k| k.ends_with("A"));
+
+        // NOTE: the path from each starting node eventually becomes cyclic, passing through
+        // terminating nodes at fixed offsets within the cycle. All ghosts are on a terminating
+        // node at the same step when that step matches one of those offsets for every ghost,
+        // which is a system of congruences solved by the chinese remainder theorem.
+        let ghosts = starting_nodes
+            .map(|n| map.ghost_cycle(n))
+            .collect::<Vec<_>>();
+        let min_step = ghosts.iter().map(|g| g.cycle.mu).max().unwrap_or(0);
+        // until every ghost is in its cycle the steps don't repeat, so they are checked one by one
+        if let Some(step) = (0..min_step).find(|&step| ghosts.iter().all(|g| g.finishes_at(step))) {
+            return Ok(step);
+        }
+        let step = align_ghosts(&ghosts, (0, 1), min_step as i64)
+            .context("ghosts never arrive at terminating nodes simultaneously")?;
+        Ok(step as usize)
+    }
+}
+
+#[derive(Debug)]
+struct GhostCycle {
+    cycle: Cycle,
+    // whether each step up to the end of the first cycle lands on a terminating node
+    finishes: Vec<bool>,
+}
+
+impl GhostCycle {
+    fn finishes_at(&self, step: usize) -> bool {
+        self.finishes[self.cycle.reduce_index(step)]
+    }
+
+    // steps within the first cycle that land on a terminating node
+    fn finish_steps(&self) -> impl Iterator<Item = usize> + '_ {
+        (self.cycle.mu..self.finishes.len()).filter(|&step| self.finishes[step])
+    }
+}
+
+// Finds the smallest step >= min_step that is x (mod m) and also lands every ghost on a
+// terminating node.
+fn align_ghosts(ghosts: &[GhostCycle], (x, m): (i64, i64), min_step: i64) -> Option<i64> {
+    let Some((ghost, rest)) = ghosts.split_first() else {
+        let k = if x < min_step {
+            (min_step - x + m - 1) / m
+        } else {
+            0
+        };
+        return Some(x + k * m);
+    };
+    ghost
+        .finish_steps()
+        .filter_map(|step| {
+            let congruence = math::crt([(x, m), (step as i64, ghost.cycle.lambda as i64)]).ok()?;
+            align_ghosts(rest, congruence, min_step)
+        })
+        .min()
+}
+
+#[derive(Debug)]
+pub struct Map<'i> {
+    instructions: &'i str,
+    network: BTreeMap<&'i str, (&'i str, &'i str)>,
+}
+
+impl<'i> Map<'i> {
+    fn parse(input: &'i str) -> Result<Self> {
+        let parse_instructions = context("instructions", is_not("\n"));
+        let parse_comma_separated = separated_pair(alphanumeric1, tag(", "), alphanumeric1);
+        let parse_network_tuple = delimited(
+            complete::char('('),
+            parse_comma_separated,
+            complete::char(')'),
+        );
+        let parse_network_line = context(
+            "network node",
+            separated_pair(alphanumeric1, tag(" = "), parse_network_tuple),
+        );
+        let parse_network = map(separated_list1(line_break, cut(parse_network_line)), |x| {
+            BTreeMap::from_iter(x)
+        });
+        let parser = map(
+            terminated(
+                separated_pair(parse_instructions, count(newline, 2), parse_network),
+                opt(newline),
+            ),
+            |(instructions, network)| Self {
+                instructions,
+                network,
+            },
+        );
+        must_parse(parser, input)
+    }
+
+    fn ins_iter(&'i self) -> impl Iterator<Item = Ins> + 'i {
+        self.instructions
+            .chars()
+            .map(|ch| match ch {
+                'L' => Ins::Left,
+                'R' => Ins::Right,
+                unknown => panic!("invalid instruction char: {unknown}"),
+            })
+            .cycle()
+    }
+
+    fn ghost_cycle(&'i self, starting_node: &'i str) -> GhostCycle {
+        // the state has to include the position within the instructions, since the same node
+        // can be left in a different direction on the next visit
+        let instructions = self.instructions.as_bytes();
+        let next_state = |&(node, idx): &(&'i str, usize)| {
+            let (left, right) = self.lookup(node);
+            let next = if instructions[idx] == b'L' {
+                left
+            } else {
+                right
+            };
+            (next, (idx + 1) % instructions.len())
+        };
+        let (cycle, states) = cycle::hashed(&(starting_node, 0), next_state);
+        let finishes = states.iter().map(|(node, _)| node.ends_with('Z')).collect();
+        GhostCycle { cycle, finishes }
+    }
+
+    fn lookup(&'i self, node_name: &str) -> (&'i str, &'i str) {
+        self.network[node_name]
+    }
+}
+
+#[derive(Debug)]
+enum Ins {
+    Left,
+    Right,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT1: &str = r#"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)"#;
+
+    const INPUT2: &str = r#"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)"#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day08.solve_part_one(INPUT1)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day08.solve_part_two(INPUT2)?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_finish_before_cycle() -> Result<()> {
+        // the ghost passes 11Z once on its way into the XXX loop
+        let input = "L\n\n11A = (11Z, 11Z)\n11Z = (XXX, XXX)\nXXX = (XXX, XXX)";
+        assert_eq!(Day08.solve_part_two(input)?, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt::Formatter;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    geom::{polygon, Direction},
+    grid::{AsChar, Grid},
+    Solution,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
+
+    type Input<'i> = Maze;
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse(&self, input: &str) -> Result<Maze> {
+        Maze::parse(input)
+    }
+
+    fn part_one(&self, maze: &Maze) -> Result<usize> {
+        Ok(maze.main_loop().count() / 2)
+    }
+
+    fn part_two(&self, maze: &Maze) -> Result<u64> {
+        let main_loop_coords: Vec<(usize, usize)> = maze.main_loop().collect();
+        polygon::interior_points(&main_loop_coords)
+    }
+}
+
+#[derive(Debug)]
+pub struct Maze {
+    tiles: Grid<Tile>,
+}
+
+impl std::fmt::Display for Maze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.tiles.fmt(f)
+    }
+}
+
+impl Maze {
+    fn parse(input: &str) -> Result<Self> {
+        let tiles = Grid::parse_with(input, Tile::try_from).context("tile parsing failed")?;
+        Ok(Self { tiles })
+    }
+
+    fn new_direction(&self, pos: (usize, usize), old_dir: Direction) -> Direction {
+        match self.tiles[pos] {
+            Tile::Pipe(d1, d2) => {
+                if old_dir == d1 {
+                    d2
+                } else if old_dir == d2 {
+                    d1
+                } else {
+                    panic!(
+                        "got into pipe pos: {pos:?}, with old_dir; {old_dir:?}, which is not valid"
+                    )
+                }
+            }
+            _ => {
+                unreachable!(
+                    "got into non-pipe pos: {pos:?}, with old_dir; {old_dir:?}, which is not valid"
+                )
+            }
+        }
+    }
+
+    fn navigate<'a>(
+        &'a self,
+        start: (usize, usize),
+        starting_direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        std::iter::successors(Some((start, starting_direction)), move |(pos, dir)| {
+            self.tiles.neighbor(*pos, *dir).and_then(|new_pos| {
+                if self.is_connected(*pos, *dir, new_pos) && new_pos != start {
+                    Some((new_pos, self.new_direction(new_pos, !*dir)))
+                } else {
+                    None
+                }
+            })
+        })
+        .map(|(pos, _)| pos)
+    }
+
+    fn is_connected(&self, start: (usize, usize), dir: Direction, dest: (usize, usize)) -> bool {
+        use Tile::*;
+        if start == dest {
+            return false;
+        }
+
+        match (&self.tiles[start], dir, &self.tiles[dest]) {
+            (_, dir, Pipe(exit1, exit2)) => *exit1 == !dir || *exit2 == !dir,
+            (Pipe(exit1, exit2), dir, Start) => *exit1 == dir || *exit2 == dir,
+            _ => false,
+        }
+    }
+
+    fn valid_directions(&self, start: (usize, usize)) -> Vec<Direction> {
+        Direction::ALL
+            .iter()
+            .copied()
+            .filter(|d| {
+                self.tiles
+                    .neighbor(start, *d)
+                    .map(|new_pos| self.is_connected(start, *d, new_pos))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn main_loop<'a>(&'a self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let start = self.starting_pos();
+        let starting_direction = self
+            .valid_directions(start)
+            .first()
+            .copied()
+            .expect("cannot move from starting position");
+        self.navigate(start, starting_direction)
+    }
+
+    fn starting_pos(&self) -> (usize, usize) {
+        self.tiles
+            .position(|t| t == &Tile::Start)
+            .expect("start position not found in maze")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tile {
+    Ground,
+    Start,
+    Pipe(Direction, Direction),
+}
+
+impl AsChar for Tile {
+    fn as_char(&self) -> char {
+        use Direction::*;
+        use Tile::*;
+        match self {
+            Pipe(Up, Down) | Pipe(Down, Up) => '|',
+            Pipe(Right, Left) | Pipe(Left, Right) => '-',
+            Pipe(Up, Right) | Pipe(Right, Up) => 'L',
+            Pipe(Up, Left) | Pipe(Left, Up) => 'J',
+            Pipe(Down, Left) | Pipe(Left, Down) => '7',
+            Pipe(Down, Right) | Pipe(Right, Down) => 'F',
+            Ground => '.',
+            Start => 'S',
+            unknown => panic!("unsupported Tile config: {unknown:?}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(ch: char) -> std::prelude::v1::Result<Self, Self::Error> {
+        use Direction::*;
+        use Tile::*;
+        Ok(match ch {
+            '|' => Pipe(Up, Down),
+            '-' => Pipe(Right, Left),
+            'L' => Pipe(Up, Right),
+            'J' => Pipe(Up, Left),
+            '7' => Pipe(Down, Left),
+            'F' => Pipe(Down, Right),
+            '.' => Ground,
+            'S' => Start,
+            unknown => anyhow::bail!("unknown tile type: {unknown:?}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT1: &str = r#".....
+.S-7.
+.|.|.
+.L-J.
+....."#;
+
+    const INPUT2: &str = r#"..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ..."#;
+
+    const INPUT3: &str = r#"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."#;
+
+    const INPUT4: &str = r#"..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+.........."#;
+
+    const INPUT5: &str = r#".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ..."#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day10.solve_part_one(INPUT1)?, 4);
+        assert_eq!(Day10.solve_part_one(INPUT2)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day10.solve_part_two(INPUT3)?, 4);
+        assert_eq!(Day10.solve_part_two(INPUT4)?, 4);
+        assert_eq!(Day10.solve_part_two(INPUT5)?, 8);
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    cycle,
+    grid::{Grid, Pos},
+    Solution,
+};
+use anyhow::Result;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Parabolic Reflector Dish";
+
+    type Input<'i> = Puzzle;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, input: &str) -> Result<Puzzle> {
+        Puzzle::parse(input)
+    }
+
+    fn part_one(&self, p: &Puzzle) -> Result<u32> {
+        let mut p = p.clone();
+        p.slide_north();
+        Ok(p.compute_score())
+    }
+
+    fn part_two(&self, p: &Puzzle) -> Result<u32> {
+        let p = cycle::nth_state_hashed(p, Puzzle::tilt_cycle, 1_000_000_000);
+        Ok(p.compute_score())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Puzzle {
+    grid: Grid<u8>,
+}
+
+impl Puzzle {
+    fn parse(input: &str) -> Result<Self> {
+        let grid = Grid::parse_with(input, |ch| match ch {
+            'O' | '.' | '#' => Ok(ch as u8),
+            unknown => anyhow::bail!("unknown char: {unknown:?}"),
+        })?;
+        Ok(Self { grid })
+    }
+
+    fn tilt_cycle(&self) -> Self {
+        let mut clone = self.clone();
+        clone.slide_north();
+        clone.slide_west();
+        clone.slide_south();
+        clone.slide_east();
+        clone
+    }
+
+    fn slide_north(&mut self) {
+        for col_idx in 0..self.grid.num_cols() {
+            let line: Vec<_> = (0..self.grid.num_rows()).map(|r| (r, col_idx)).collect();
+            self.slide(&line);
+        }
+    }
+
+    fn slide_south(&mut self) {
+        for col_idx in 0..self.grid.num_cols() {
+            let line: Vec<_> = (0..self.grid.num_rows())
+                .rev()
+                .map(|r| (r, col_idx))
+                .collect();
+            self.slide(&line);
+        }
+    }
+
+    fn slide_west(&mut self) {
+        for row_idx in 0..self.grid.num_rows() {
+            let line: Vec<_> = (0..self.grid.num_cols()).map(|c| (row_idx, c)).collect();
+            self.slide(&line);
+        }
+    }
+
+    fn slide_east(&mut self) {
+        for row_idx in 0..self.grid.num_rows() {
+            let line: Vec<_> = (0..self.grid.num_cols())
+                .rev()
+                .map(|c| (row_idx, c))
+                .collect();
+            self.slide(&line);
+        }
+    }
+
+    // Rolls every rock in `line` towards its first position until it hits a cube rock or another
+    // rolled rock.
+    fn slide(&mut self, line: &[Pos]) {
+        let mut slot_idx = 0;
+        for (idx, &pos) in line.iter().enumerate() {
+            match self.grid[pos] {
+                b'#' => slot_idx = idx + 1,
+                b'O' => {
+                    if slot_idx != idx {
+                        self.grid[line[slot_idx]] = b'O';
+                        self.grid[pos] = b'.';
+                    }
+                    slot_idx += 1;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    // for each rock, sum the distance from the "southern" edge
+    fn compute_score(&self) -> u32 {
+        let total_len = self.grid.num_rows();
+        self.grid
+            .enumerate()
+            .filter_map(|((row, _), &ch)| (ch == b'O').then_some((total_len - row) as u32))
+            .sum()
+    }
+}
+
+impl std::fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#...."#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        let p = Day14.parse(INPUT)?;
+        assert_eq!(Day14.part_one(&p)?, 136);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycle() -> Result<()> {
+        let p = Puzzle::parse(INPUT)?;
+        let expected = vec![
+            r#".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."#,
+            r#".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#..OO###..
+#.OOO#...O"#,
+            r#".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O"#,
+        ];
+
+        let p = p.tilt_cycle();
+        assert_eq!(p, Puzzle::parse(expected[0])?);
+        let p = p.tilt_cycle();
+        assert_eq!(p, Puzzle::parse(expected[1])?);
+        let p = p.tilt_cycle();
+        assert_eq!(p, Puzzle::parse(expected[2])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let p = Day14.parse(INPUT)?;
+        assert_eq!(Day14.part_two(&p)?, 64);
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::{Context, Result};
+
+use crate::{geom::Direction, grid::Grid, Solution};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "The Floor Will Be Lava";
+
+    type Input<'i> = Puzzle;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Puzzle> {
+        input.parse()
+    }
+
+    fn part_one(&self, p: &Puzzle) -> Result<usize> {
+        let start = Head {
+            pos: (0, 0),
+            heading: Direction::Right,
+        };
+        let mask = p.energize(start);
+        Ok(mask.num_energized())
+    }
+
+    fn part_two(&self, p: &Puzzle) -> Result<usize> {
+        p.start_positions()
+            .map(|head| p.energize(head).num_energized())
+            .max()
+            .context("no start positions")
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    grid: Grid<u8>,
+}
+
+impl Puzzle {
+    fn energize(&self, start: Head) -> TileMask<'_> {
+        let mut mask = TileMask {
+            puzzle: self,
+            directions: self.grid.map(|_| Vec::new()),
+        };
+
+        let mut heads = vec![start];
+        while !heads.is_empty() {
+            heads.retain(|head| {
+                let Some(valid_pos) = self.grid.checked_pos(head.pos.0, head.pos.1) else {
+                    return false;
+                };
+                if mask.directions[valid_pos].contains(&head.heading) {
+                    return false;
+                } else {
+                    mask.directions[valid_pos].push(head.heading);
+                    return true;
+                }
+            });
+            let new_heads = heads
+                .iter_mut()
+                .filter_map(|head| head.step(self))
+                .collect::<Vec<_>>();
+            heads.extend(new_heads);
+        }
+        mask
+    }
+
+    fn start_positions(&self) -> impl Iterator<Item = Head> {
+        let num_cols = self.grid.num_cols() as isize;
+        let num_rows = self.grid.num_rows() as isize;
+        (0..num_cols)
+            .map(|c| Head {
+                pos: (0, c),
+                heading: Direction::Down,
+            })
+            .chain((0..num_cols).map(move |c| Head {
+                pos: (num_rows - 1, c),
+                heading: Direction::Up,
+            }))
+            .chain((0..num_rows).map(move |r| Head {
+                pos: (r, 0),
+                heading: Direction::Right,
+            }))
+            .chain((0..num_rows).map(move |r| Head {
+                pos: (r, num_cols - 1),
+                heading: Direction::Left,
+            }))
+    }
+}
+
+impl std::str::FromStr for Puzzle {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let grid = Grid::parse(input)?;
+        Ok(Self { grid })
+    }
+}
+
+impl std::fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+struct TileMask<'p> {
+    puzzle: &'p Puzzle,
+    directions: Grid<Vec<Direction>>,
+}
+impl TileMask<'_> {
+    fn num_energized(&self) -> usize {
+        self.directions.iter().filter(|dirs| dirs.len() > 0).count()
+    }
+}
+
+impl std::fmt::Display for TileMask<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (row, dirs_row) in self.directions.rows().enumerate() {
+            for (col, dirs) in dirs_row.iter().enumerate() {
+                match self.puzzle.grid[(row, col)] {
+                    b'.' => match dirs.len().cmp(&1) {
+                        std::cmp::Ordering::Less => write!(f, "."),
+                        std::cmp::Ordering::Equal => write!(f, "{}", dirs[0]),
+                        std::cmp::Ordering::Greater => write!(f, "{}", dirs.len()),
+                    }?,
+                    tile => {
+                        write!(f, "{}", tile as char)?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Head {
+    pos: (isize, isize),
+    heading: Direction,
+}
+
+impl Head {
+    fn step(&mut self, puzzle: &Puzzle) -> Option<Self> {
+        use Direction::*;
+        let hrow = &mut self.pos.0;
+        let hcol = &mut self.pos.1;
+        let mut new_head = None;
+
+        let Some(valid_pos) = puzzle.grid.checked_pos(*hrow, *hcol) else {
+            return None;
+        };
+        self.heading = match (puzzle.grid[valid_pos], self.heading) {
+            (b'\\', Up) => Left,
+            (b'\\', Down) => Right,
+            (b'\\', Left) => Up,
+            (b'\\', Right) => Down,
+            (b'/', Up) => Right,
+            (b'/', Down) => Left,
+            (b'/', Left) => Down,
+            (b'/', Right) => Up,
+            (b'-', Up | Down) => {
+                new_head = Some(Self {
+                    pos: (*hrow, *hcol + 1),
+                    heading: Right,
+                });
+                Left
+            }
+            (b'|', Left | Right) => {
+                new_head = Some(Self {
+                    pos: (*hrow + 1, *hcol),
+                    heading: Down,
+                });
+                Up
+            }
+            _other => self.heading,
+        };
+        let delta = self.heading.delta::<isize>();
+        *hrow += delta.y;
+        *hcol += delta.x;
+        new_head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|...."#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day16.solve_part_one(INPUT)?, 46);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day16.solve_part_two(INPUT)?, 51);
+        Ok(())
+    }
+}
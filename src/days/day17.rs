@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    geom::Direction,
+    grid::{Col, Grid, Row},
+    search::{self, Path},
+    Solution,
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Clumsy Crucible";
+
+    type Input<'i> = Puzzle;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, input: &str) -> Result<Puzzle> {
+        input.parse()
+    }
+
+    fn part_one(&self, p: &Puzzle) -> Result<u32> {
+        let path = p.min_heat_loss(1, 3).context("path to goal not found")?;
+        crate::trace!(Debug, point = "path", "\n{}", PathPrinter(p, &path));
+        Ok(path.cost)
+    }
+
+    fn part_two(&self, p: &Puzzle) -> Result<u32> {
+        let path = p.min_heat_loss(4, 10).context("path to goal not found")?;
+        Ok(path.cost)
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    grid: Grid<u8>,
+}
+
+impl Puzzle {
+    fn goal(&self) -> (Row, Col) {
+        (self.grid.num_rows() - 1, self.grid.num_cols() - 1)
+    }
+
+    fn min_heat_loss(&self, min_step: u8, max_step: u8) -> Option<Path<Crucible, u32>> {
+        let goal = self.goal();
+        let starts = [Direction::Right, Direction::Down].map(|dir| Crucible {
+            pos: (0, 0),
+            dir,
+            num_steps: 0,
+        });
+        let h = |c: &Crucible| (c.pos.0.abs_diff(goal.0) + c.pos.1.abs_diff(goal.1)) as u32;
+        // the crucible can only stop at the goal once it's allowed to turn
+        let is_goal = |c: &Crucible| c.pos == goal && c.num_steps >= min_step;
+        search::a_star(starts, |c| self.moves(c, min_step, max_step), h, is_goal)
+    }
+
+    fn moves(&self, c: &Crucible, min_step: u8, max_step: u8) -> Vec<(Crucible, u32)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|dir| {
+                if dir == !c.dir {
+                    // can't go backwards
+                    return None;
+                }
+                let num_steps = if dir == c.dir {
+                    if c.num_steps < max_step {
+                        // can go straight
+                        c.num_steps + 1
+                    } else {
+                        // can't go straight
+                        return None;
+                    }
+                } else if c.num_steps >= min_step {
+                    // can turn, reset the counter
+                    1
+                } else {
+                    // can't turn yet
+                    return None;
+                };
+                let pos = self.grid.neighbor(c.pos, dir)?;
+                let heat_loss = (self.grid[pos] - b'0') as u32;
+                Some((
+                    Crucible {
+                        pos,
+                        dir,
+                        num_steps,
+                    },
+                    heat_loss,
+                ))
+            })
+            .collect()
+    }
+}
+
+impl std::str::FromStr for Puzzle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, |ch| {
+            anyhow::ensure!(ch.is_ascii_digit(), "expected a digit, got {ch:?}");
+            Ok(ch as u8)
+        })?;
+        Ok(Self { grid })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Crucible {
+    pos: (Row, Col),
+    dir: Direction,
+    num_steps: u8,
+}
+
+#[derive(Debug)]
+struct PathPrinter<'a>(&'a Puzzle, &'a Path<Crucible, u32>);
+
+impl std::fmt::Display for PathPrinter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let &Self(puzzle, path) = self;
+        let moves: BTreeMap<(Row, Col), Direction> =
+            path.states.iter().map(|c| (c.pos, c.dir)).collect();
+        for (r, row) in puzzle.grid.rows().enumerate() {
+            for (c, heat_loss) in row.iter().enumerate() {
+                match moves.get(&(r, c)) {
+                    Some(dir) => write!(f, "{dir}")?,
+                    None => write!(f, "{}", *heat_loss as char)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "heat loss: {}", path.cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BinaryHeap;
+
+    use super::*;
+
+    const INPUT: &str = r#"2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day17.solve_part_one(INPUT)?, 102);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day17.solve_part_two(INPUT)?, 94);
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_heap() {
+        let mut h = BinaryHeap::new();
+        h.push(1);
+        h.push(2);
+        h.push(1);
+        assert_eq!(h.len(), 3);
+    }
+}
+// 2>>34^>>>1323 04 05       23 25 28 29
+// 32v>>>35v5623    06 11 15 20       32
+// 32552456v>>54                      37 41 43
+// 3446585845v52                            47
+// 4546657867v>6                            52 55
+// 14385987984v4                               60
+// 44578769877v6                               66
+// 36378779796v>                               71 74
+// 465496798688v                                  81
+// 456467998645v                                  84
+// 12246868655<v                                  93 87
+// 25465488877v5                                  96
+// 43226746555v>                                  99 102
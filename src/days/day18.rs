@@ -0,0 +1,159 @@
+use crate::{
+    geom::{polygon, Direction, Vec2},
+    must_parse,
+    parse::line_break,
+    Solution,
+};
+use anyhow::Result;
+use nom::{
+    bytes::complete::{is_a, tag},
+    character::complete::{self, one_of, space1},
+    combinator::{cut, map, map_res},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, tuple},
+};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Lavaduct Lagoon";
+
+    type Input<'i> = Puzzle<'i>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Puzzle<'i>> {
+        Puzzle::parse(input)
+    }
+
+    fn part_one(&self, p: &Puzzle) -> Result<u64> {
+        compute_lava_cubes(&p.as_coords())
+    }
+
+    fn part_two(&self, p: &Puzzle) -> Result<u64> {
+        compute_lava_cubes(&p.as_corrected().as_coords())
+    }
+}
+
+fn compute_lava_cubes(coords: &[(i64, i64)]) -> Result<u64> {
+    // the trench is one cube wide and follows the lattice points of the polygon's edges, so the
+    // lagoon holds every interior point plus the boundary itself
+    Ok(polygon::interior_points(coords)? + polygon::boundary_points(coords)?)
+}
+
+#[derive(Debug)]
+pub struct Puzzle<'i> {
+    ins: Vec<Instruction<'i>>,
+}
+impl<'i> Puzzle<'i> {
+    fn parse(input: &'i str) -> Result<Self> {
+        let parse_direction = context("direction", map_res(one_of("UDRL"), Direction::try_from));
+        let parse_color = context(
+            "color",
+            delimited(tag("(#"), is_a("0123456789abcdef"), complete::char(')')),
+        );
+        let parse_ins = map(
+            tuple((
+                parse_direction,
+                space1,
+                context("distance", complete::u64),
+                space1,
+                parse_color,
+            )),
+            |(dir, _, count, _, color)| Instruction { dir, count, color },
+        );
+        let parse_instructions =
+            separated_list1(line_break, cut(context("instruction", parse_ins)));
+        let parser = map(parse_instructions, |ins: Vec<Instruction>| Self { ins });
+        must_parse(parser, input)
+    }
+
+    fn as_coords(&self) -> Vec<(i64, i64)> {
+        let iter = self.ins.iter().scan(Vec2::default(), |pos, ins| {
+            *pos += ins.dir.delta() * ins.count as i64;
+            Some((pos.x, pos.y))
+        });
+        let mut ret = vec![(0, 0)];
+        ret.extend(iter);
+        ret
+    }
+
+    fn as_corrected(&self) -> Self {
+        let ins = self
+            .ins
+            .iter()
+            .map(|ins| {
+                let count = u64::from_str_radix(&ins.color[..5], 16).expect("dist parsing failed");
+                let dir = match ins.color.as_bytes()[5] {
+                    b'0' => Direction::Right,
+                    b'1' => Direction::Down,
+                    b'2' => Direction::Left,
+                    b'3' => Direction::Up,
+                    other => unreachable!("unexpected direction: {}", other),
+                };
+                Instruction {
+                    dir,
+                    count,
+                    color: ins.color,
+                }
+            })
+            .collect();
+        Self { ins }
+    }
+}
+
+#[derive(Debug)]
+struct Instruction<'i> {
+    dir: Direction,
+    count: u64,
+    color: &'i str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)"#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day18.solve_part_one(INPUT)?, 62);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day18.solve_part_two(INPUT)?, 952408144115);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Puzzle::parse(&INPUT.replace("D 5 (#0dc571)", "Q 5 (#0dc571)")).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "line 2, column 1: expected one of the allowed characters while parsing direction while parsing instruction\n"
+        ));
+    }
+
+    #[test]
+    fn test_coords() -> Result<()> {
+        let p = Puzzle::parse(INPUT)?;
+        let _coords = p.as_coords();
+        Ok(())
+    }
+}
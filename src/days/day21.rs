@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+
+use crate::{
+    grid::{AsChar, Grid, Pos},
+    search, Solution,
+};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Step Counter";
+
+    type Input<'i> = Puzzle;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Puzzle> {
+        let mut p = Puzzle::parse(input)?;
+        p.compute_min_steps();
+        Ok(p)
+    }
+
+    fn part_one(&self, p: &Puzzle) -> Result<usize> {
+        Ok(p.num_reachable_tiles(64))
+    }
+
+    fn part_two(&self, p: &Puzzle) -> Result<usize> {
+        Ok(p.compute_reachable_tiles(26501365))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Grass = b'.' as isize,
+    Stone = b'#' as isize,
+    Start = b'S' as isize,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(ch: char) -> Result<Self> {
+        Ok(match ch {
+            '.' => Self::Grass,
+            '#' => Self::Stone,
+            'S' => Self::Start,
+            unknown => anyhow::bail!("invalid tile: {unknown:?}"),
+        })
+    }
+}
+
+impl AsChar for Tile {
+    fn as_char(&self) -> char {
+        *self as u8 as char
+    }
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    tiles: Grid<Tile>,
+    start_pos: Pos,
+    min_steps_needed: Grid<Option<usize>>,
+}
+
+impl Puzzle {
+    fn parse(input: &str) -> Result<Self> {
+        let tiles = Grid::parse_with(input, Tile::try_from)?;
+        let start_pos = tiles
+            .position(|t| t == &Tile::Start)
+            .context("start tile is missing")?;
+        Ok(Self {
+            min_steps_needed: tiles.map(|_| None),
+            start_pos,
+            tiles,
+        })
+    }
+
+    // See: https://github.com/villuna/aoc23/wiki/A-Geometric-solution-to-advent-of-code-2023,-day-21
+    fn compute_min_steps(&mut self) {
+        // BFS to find the shortest path to each tile
+        self.min_steps_needed =
+            search::grid_bfs(&self.tiles, [self.start_pos], |t| t != &Tile::Stone);
+    }
+
+    fn min_steps(&self) -> impl Iterator<Item = u32> + '_ {
+        self.min_steps_needed
+            .iter()
+            .flatten()
+            .map(|&steps| steps as u32)
+    }
+
+    fn num_reachable_tiles(&self, num_steps: u32) -> usize {
+        let num_steps = num_steps as usize;
+        let neighbors = |&pos: &Pos| {
+            self.tiles
+                .neighbors4(pos)
+                .filter(|&next| self.tiles[next] != Tile::Stone)
+        };
+        // stepping back and forth wastes two steps at a time, so every tile reachable within
+        // num_steps with the same parity can be ended on
+        search::Bfs::new([self.start_pos], neighbors)
+            .within(num_steps)
+            .filter(|(_, steps)| steps % 2 == num_steps % 2)
+            .count()
+    }
+
+    fn compute_reachable_tiles(&self, num_steps: u32) -> usize {
+        assert!(
+            num_steps as usize >= self.tiles.num_cols(),
+            "use num_reachable_tiles instead"
+        );
+        // the row and column that the starting tile is on is unbostructed: there are no stones
+        // This means that if we can take num_steps straight in up, down, left or right and count how
+        // many times the original map repeats.
+        let steps_to_edge = num_steps % self.tiles.num_cols() as u32;
+
+        let even_corners = self
+            .min_steps()
+            .filter(|v| *v % 2 == 0 && *v > steps_to_edge)
+            .count();
+        let odd_corners = self
+            .min_steps()
+            .filter(|v| *v % 2 == 1 && *v > steps_to_edge)
+            .count();
+
+        let even_full = self.min_steps().filter(|v| *v % 2 == 0).count();
+        let odd_full = self.min_steps().filter(|v| *v % 2 == 1).count();
+
+        let num_cols = self.tiles.num_cols();
+        let n = ((num_steps as usize) - (num_cols / 2)) / num_cols;
+
+        ((n + 1) * (n + 1)) * odd_full + (n * n) * even_full - (n + 1) * odd_corners
+            + n * even_corners
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug)]
+struct TilePrinter<'i>(&'i Puzzle);
+
+impl std::fmt::Display for TilePrinter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let puzzle = self.0;
+        for row in 0..puzzle.tiles.num_rows() {
+            for col in 0..puzzle.tiles.num_cols() {
+                if let Some(min_steps) = puzzle.min_steps_needed[(row, col)] {
+                    write!(f, "{}", min_steps)?;
+                } else {
+                    write!(f, "{}", puzzle.tiles[(row, col)].as_char())?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+..........."#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        let mut p = Puzzle::parse(INPUT)?;
+        p.compute_min_steps();
+        let ks = [(1, 2), (2, 4), (3, 6), (6, 16)];
+        for (num_steps, expected) in ks {
+            let n = p.num_reachable_tiles(num_steps);
+            assert_eq!(n, expected, "num_steps: {num_steps}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,539 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+use crate::{
+    bit_set::{BitSet, Words},
+    grid::Grid,
+    search, Solution,
+};
+use anyhow::Result;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "A Long Walk";
+
+    type Input<'i> = Puzzle;
+    type PartOne = u16;
+    type PartTwo = u16;
+
+    fn parse(&self, input: &str) -> Result<Puzzle> {
+        Puzzle::parse(input)
+    }
+
+    fn part_one(&self, p: &Puzzle) -> Result<u16> {
+        p.longest_path(Strategy::current())
+    }
+
+    fn part_two(&self, p: &Puzzle) -> Result<u16> {
+        p.without_slopes().longest_path(Strategy::current())
+    }
+}
+
+// How to search for the longest path, picked with the `--strategy` option of the runners so that
+// they can be compared on the same input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // DFS over every simple path to the finish
+    Exhaustive,
+    // DFS that abandons paths which can't beat the longest one found so far
+    Pruned,
+    // Pruned, with the first few levels of the DFS fanned out over threads
+    Parallel,
+}
+
+static STRATEGY: OnceLock<Strategy> = OnceLock::new();
+
+// Picks the strategy for the rest of the process, failing when one was already picked.
+pub fn set_strategy(strategy: Strategy) -> Result<()> {
+    STRATEGY
+        .set(strategy)
+        .map_err(|_| anyhow::format_err!("day 23 strategy is already set"))
+}
+
+impl Strategy {
+    // Pruned unless picked otherwise; threads don't pay off on small inputs.
+    fn current() -> Self {
+        STRATEGY.get().copied().unwrap_or(Self::Pruned)
+    }
+}
+
+impl std::str::FromStr for Strategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "exhaustive" => Self::Exhaustive,
+            "pruned" => Self::Pruned,
+            "parallel" => Self::Parallel,
+            unknown => anyhow::bail!(
+                "unknown strategy {unknown:?}, expected exhaustive, pruned or parallel"
+            ),
+        })
+    }
+}
+
+type Pos = (isize, isize);
+
+#[derive(Debug)]
+struct CostLut(Vec<Vec<u16>>);
+
+impl CostLut {
+    // Yields the indices in `indices` along with the cost of getting there from `from_idx`.
+    unsafe fn get<'a, W: Words>(
+        &'a self,
+        from_idx: usize,
+        indices: &'a BitSet<W>,
+    ) -> impl Iterator<Item = (usize, u16)> + 'a {
+        let costs = self.0.get_unchecked(from_idx);
+        indices.iter().map(|idx| (idx, *costs.get_unchecked(idx)))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Graph {
+    neighbors: BTreeMap<Pos, BTreeSet<Pos>>,
+    edges: BTreeMap<(Pos, Pos), u16>,
+}
+
+impl Graph {
+    fn add_directed_edge(&mut self, n1: Pos, n2: Pos, cost: u16) {
+        self.edges.entry((n1, n2)).or_insert(cost);
+        self.neighbors.entry(n1).or_default().insert(n2);
+    }
+
+    fn get_sorted_nodes(&self) -> Vec<Pos> {
+        self.edges
+            .keys()
+            .cloned()
+            .flat_map(|(n1, n2)| [n1, n2])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn get_neighbor_bitsets<W: Words>(&self, nodes: &[Pos]) -> Vec<BitSet<W>> {
+        let build_bitset = |node| {
+            let Some(neighbors) = self.neighbors.get(node) else {
+                return BitSet::new();
+            };
+            let find_position = |neighbor| {
+                nodes
+                    .iter()
+                    .position(|x| x == neighbor)
+                    .expect("all neighbors are expected to be in nodes")
+            };
+            BitSet::from_iter(neighbors.iter().map(find_position))
+        };
+        nodes.iter().map(build_bitset).collect()
+    }
+
+    fn get_costs_lookup_table(&self, nodes: &[Pos]) -> CostLut {
+        let mut lut = vec![vec![0u16; nodes.len()]; nodes.len()];
+        for ((from, to), cost) in &self.edges {
+            let from_idx = nodes
+                .iter()
+                .position(|n| n == from)
+                .expect("from node not found");
+            let to_idx = nodes
+                .iter()
+                .position(|n| n == to)
+                .expect("to node not found");
+            lut[from_idx][to_idx] = *cost;
+        }
+        CostLut(lut)
+    }
+}
+
+impl std::fmt::Display for Graph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Graph:")?;
+        for ((from, to), cost) in &self.edges {
+            writeln!(
+                f,
+                "\t{from:?} --{cost}--> {to:?}, neighbors: {:?}",
+                self.neighbors.get(from),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// A node to continue the DFS from, along with the nodes already on the path leading to it.
+#[derive(Debug, Clone)]
+struct State<W> {
+    node_idx: usize,
+    seen: BitSet<W>,
+    cost: u16,
+    // upper bound on the cost still to be added by nodes not yet on the path
+    remaining: u32,
+}
+
+// The DFS over the junction graph, with nodes referred to by their index in the sorted nodes.
+struct Walk<W> {
+    neighbors: Vec<BitSet<W>>,
+    costs: CostLut,
+    strategy: Strategy,
+    start_idx: usize,
+    finish_idx: usize,
+    // the node that ends a path: either the finish itself or, when the finish has a single way
+    // in, the node before it since any path reaching that node has to take the last edge next
+    goal_idx: usize,
+    goal_cost: u16,
+    // the most expensive edge into each node; every node after the start is entered exactly once,
+    // so the sum of these over the unseen nodes bounds how much longer a path can get
+    max_entry_costs: Vec<u16>,
+}
+
+// The number of DFS levels expanded before handing the remaining subtrees out to threads.
+const FAN_OUT_DEPTH: usize = 4;
+
+impl<W: Words + Send + Sync> Walk<W> {
+    fn new(graph: &Graph, nodes: &[Pos], strategy: Strategy) -> Self {
+        let neighbors = graph.get_neighbor_bitsets::<W>(nodes);
+        let costs = graph.get_costs_lookup_table(nodes);
+
+        // nodes are sorted by row number first, hence start and finish end up as being first and last
+        // nodes respectively
+        let start_idx = 0;
+        let finish_idx = nodes.len() - 1;
+
+        let mut goal_idx = finish_idx;
+        let mut goal_cost = 0;
+        let mut ways_in = (0..nodes.len()).filter(|&idx| neighbors[idx].contains(finish_idx));
+        if let (Some(last_idx), None) = (ways_in.next(), ways_in.next()) {
+            if strategy != Strategy::Exhaustive {
+                goal_idx = last_idx;
+                goal_cost = costs.0[last_idx][finish_idx];
+            }
+        }
+
+        let max_entry_costs = (0..nodes.len())
+            .map(|to_idx| {
+                (0..nodes.len())
+                    .filter(|&from_idx| neighbors[from_idx].contains(to_idx))
+                    .map(|from_idx| costs.0[from_idx][to_idx])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        Self {
+            neighbors,
+            costs,
+            strategy,
+            start_idx,
+            finish_idx,
+            goal_idx,
+            goal_cost,
+            max_entry_costs,
+        }
+    }
+
+    fn longest_path(&self) -> Result<u16> {
+        let mut seen = BitSet::new();
+        let mut remaining = 0;
+        for idx in 0..self.neighbors.len() {
+            // when the last edge is forced, the finish is only ever entered through the goal
+            if idx == self.start_idx || (idx == self.finish_idx && self.goal_idx != idx) {
+                seen.set(idx);
+            } else {
+                remaining += self.max_entry_costs[idx] as u32;
+            }
+        }
+        let start = State {
+            node_idx: self.start_idx,
+            seen,
+            cost: 0,
+            remaining,
+        };
+
+        // the longest path found so far, or -1 before the first one
+        let longest_path = AtomicI32::new(-1);
+        match self.strategy {
+            Strategy::Exhaustive | Strategy::Pruned => self.dfs(vec![start], &longest_path),
+            Strategy::Parallel => self.dfs_parallel(start, &longest_path),
+        }
+        let longest_path = longest_path.into_inner();
+        anyhow::ensure!(longest_path >= 0, "path to finish not found");
+        Ok(longest_path as u16)
+    }
+
+    fn dfs(&self, mut q: Vec<State<W>>, longest_path: &AtomicI32) {
+        while let Some(state) = q.pop() {
+            self.expand(state, longest_path, &mut q);
+        }
+    }
+
+    fn dfs_parallel(&self, start: State<W>, longest_path: &AtomicI32) {
+        let mut frontier = vec![start];
+        for _ in 0..FAN_OUT_DEPTH {
+            let mut next = Vec::new();
+            for state in frontier {
+                self.expand(state, longest_path, &mut next);
+            }
+            frontier = next;
+        }
+
+        let num_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let frontier = Mutex::new(frontier);
+        std::thread::scope(|s| {
+            for _ in 0..num_threads {
+                s.spawn(|| loop {
+                    let Some(state) = frontier.lock().unwrap().pop() else {
+                        break;
+                    };
+                    self.dfs(vec![state], longest_path);
+                });
+            }
+        });
+    }
+
+    // Pushes the states reachable in one step from `state` onto `q`, or records the length of the
+    // path if `state` is the end of one.
+    fn expand(&self, state: State<W>, longest_path: &AtomicI32, q: &mut Vec<State<W>>) {
+        let State {
+            node_idx: from_node_idx,
+            mut seen,
+            cost,
+            remaining,
+        } = state;
+        let finish_cost = (cost + self.goal_cost) as i32;
+        if from_node_idx == self.goal_idx {
+            longest_path.fetch_max(finish_cost, Ordering::Relaxed);
+            return;
+        }
+        let upper_bound = finish_cost + remaining as i32;
+        if self.strategy != Strategy::Exhaustive
+            && upper_bound <= longest_path.load(Ordering::Relaxed)
+        {
+            return;
+        }
+        seen.set(from_node_idx);
+        let neighbors = self.neighbors[from_node_idx].difference(&seen);
+        // SAFETY: from_node_idx and neighbors indexes are all positions in nodes, which is
+        // the size of both dimensions of the lookup table
+        let costs = unsafe { self.costs.get(from_node_idx, &neighbors) };
+        for (neighbor_idx, ncost) in costs {
+            q.push(State {
+                node_idx: neighbor_idx,
+                seen: seen.clone(),
+                cost: cost + ncost,
+                remaining: remaining - self.max_entry_costs[neighbor_idx] as u32,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    tiles: Grid<u8>,
+}
+
+impl Puzzle {
+    fn parse(input: &str) -> Result<Self> {
+        let tiles = Grid::parse(input)?;
+        Ok(Self { tiles })
+    }
+
+    fn get_tile(&self, (row, col): (isize, isize)) -> Option<u8> {
+        let pos = self.tiles.checked_pos(row, col)?;
+        Some(self.tiles[pos])
+    }
+
+    fn get_neighbor_pos(&self, cur_pos: (isize, isize)) -> Vec<(isize, isize)> {
+        let mut npos = Vec::new();
+        let mut push_if_valid = |new_pos| {
+            match self.get_tile(new_pos) {
+                Some(b'<') if cur_pos != (new_pos.0, new_pos.1 - 1) => npos.push(new_pos),
+                Some(b'>') if cur_pos != (new_pos.0, new_pos.1 + 1) => npos.push(new_pos),
+                Some(b'^') if cur_pos != (new_pos.0 - 1, new_pos.1) => npos.push(new_pos),
+                Some(b'v') if cur_pos != (new_pos.0 + 1, new_pos.1) => npos.push(new_pos),
+                Some(b'.') => npos.push(new_pos),
+                _ => (),
+            };
+        };
+        match self.get_tile(cur_pos) {
+            Some(b'>') => push_if_valid((cur_pos.0, cur_pos.1 + 1)),
+            Some(b'<') => push_if_valid((cur_pos.0, cur_pos.1 - 1)),
+            Some(b'^') => push_if_valid((cur_pos.0 - 1, cur_pos.1)),
+            Some(b'v') => push_if_valid((cur_pos.0 + 1, cur_pos.1)),
+            Some(b'.') => [(0, 1), (0, -1), (-1, 0), (1, 0)]
+                .into_iter()
+                .for_each(|(dr, dc)| push_if_valid((cur_pos.0 + dr, cur_pos.1 + dc))),
+
+            Some(b'#') => (),
+            unknown => panic!("unknown tile {:?}", unknown),
+        };
+        npos
+    }
+
+    fn without_slopes(&self) -> Self {
+        let mut p = self.clone();
+        // treat all slides as normal tiles
+        for tile in p.tiles.iter_mut() {
+            match *tile {
+                b'>' | b'<' | b'^' | b'v' => *tile = b'.',
+                _ => (),
+            }
+        }
+        p
+    }
+
+    fn longest_path(&self, strategy: Strategy) -> Result<u16> {
+        let graph = self.as_graph();
+        let nodes = graph.get_sorted_nodes();
+        // a single word covers typical inputs and keeps the DFS from allocating
+        if nodes.len() <= 64 {
+            Walk::<[u64; 1]>::new(&graph, &nodes, strategy).longest_path()
+        } else {
+            Walk::<Vec<u64>>::new(&graph, &nodes, strategy).longest_path()
+        }
+    }
+
+    fn as_graph(&self) -> Graph {
+        let nodes: BTreeSet<_> = self
+            .tiles
+            .positions()
+            .map(|(r, c)| (r as isize, c as isize))
+            .filter(|(r, c)| {
+                let mut valid_npos = 0;
+                if self.get_tile((*r, *c)) != Some(b'#') {
+                    for (dr, dc) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+                        if let Some(tile) = self.get_tile((r + dr, c + dc)) {
+                            if tile != b'#' {
+                                valid_npos += 1;
+                            }
+                        }
+                    }
+                }
+                // any position that 1 neighbor is the start and finish tile
+                // 3 or 4 valid nighbors are junctions where multiple paths meet and therefore are
+                //   nodes in our graph
+                valid_npos > 0 && valid_npos != 2
+            })
+            .collect();
+
+        let mut graph = Graph::default();
+        for &start_node in &nodes {
+            // walk every corridor leaving start_node, stopping at the first node along each one
+            let corridors = search::Bfs::new([start_node], |&pos| {
+                if pos != start_node && nodes.contains(&pos) {
+                    Vec::new()
+                } else {
+                    self.get_neighbor_pos(pos)
+                }
+            });
+            for (end_node, cost) in corridors {
+                if end_node != start_node && nodes.contains(&end_node) {
+                    graph.add_directed_edge(start_node, end_node, cost as u16);
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#"#;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        assert_eq!(Day23.solve_part_one(INPUT)?, 94);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        assert_eq!(Day23.solve_part_two(INPUT)?, 154);
+        Ok(())
+    }
+
+    #[test]
+    fn test_strategies_agree() -> Result<()> {
+        let p = Puzzle::parse(INPUT)?;
+        for strategy in ["exhaustive", "pruned", "parallel"] {
+            let strategy = strategy.parse()?;
+            assert_eq!(p.longest_path(strategy)?, 94, "{strategy:?}");
+            assert_eq!(
+                p.without_slopes().longest_path(strategy)?,
+                154,
+                "{strategy:?}"
+            );
+        }
+        assert!("fastest".parse::<Strategy>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_heap_bit_sets() -> Result<()> {
+        // both widths agree on a graph that fits in a single word
+        for p in [
+            Puzzle::parse(INPUT)?,
+            Puzzle::parse(INPUT)?.without_slopes(),
+        ] {
+            let graph = p.as_graph();
+            let nodes = graph.get_sorted_nodes();
+            assert!(nodes.len() <= 64);
+            for strategy in [Strategy::Exhaustive, Strategy::Pruned, Strategy::Parallel] {
+                assert_eq!(
+                    Walk::<Vec<u64>>::new(&graph, &nodes, strategy).longest_path()?,
+                    Walk::<[u64; 1]>::new(&graph, &nodes, strategy).longest_path()?,
+                    "{strategy:?}"
+                );
+            }
+        }
+
+        // a corridor with a dead end branching off every other tile, each adding a junction and a
+        // dead end node
+        let width = 71;
+        let row = |open: &dyn Fn(usize) -> bool| -> String {
+            (0..width)
+                .map(|c| if open(c) { '.' } else { '#' })
+                .collect()
+        };
+        let input = [
+            row(&|c| c == 1 || (c >= 3 && c < width - 3 && c % 2 == 1)),
+            row(&|c| c > 0 && c < width - 1),
+            row(&|c| c == width - 2),
+        ]
+        .join("\n");
+        let p = Puzzle::parse(&input)?;
+        assert_eq!(p.as_graph().get_sorted_nodes().len(), 68);
+        for strategy in [Strategy::Exhaustive, Strategy::Pruned, Strategy::Parallel] {
+            assert_eq!(p.longest_path(strategy)?, 70, "{strategy:?}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::is_not,
+    character::complete::{char, space1},
+    combinator::map,
+    error::context,
+    sequence::tuple,
+};
+
+use crate::{days, parse::must_parse_lines};
+
+// Not `examples`, which cargo reserves for example targets.
+pub const DEFAULT_DIR: &str = "fixtures";
+
+// An example input with its expected answers, stored as `fixtures/dayNN/<name>.txt` next to a
+// `<name>.answers` sidecar. The sidecar holds one `<part> <answer>` per line; blank lines and lines
+// starting with `#` are ignored, and parts without an answer are not checked.
+#[derive(Debug)]
+pub struct Fixture {
+    pub day: u8,
+    pub name: String,
+    pub input_path: PathBuf,
+    pub expected: BTreeMap<u8, String>,
+}
+
+impl Fixture {
+    fn load(day: u8, input_path: PathBuf) -> Result<Self> {
+        let name = input_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .with_context(|| format!("invalid fixture name: {}", input_path.display()))?
+            .to_string();
+        let answers_path = input_path.with_extension("answers");
+        let answers = std::fs::read_to_string(&answers_path)
+            .with_context(|| format!("unable to read answers: {}", answers_path.display()))?;
+        let expected = parse_answers(&answers)
+            .with_context(|| format!("invalid answers file: {}", answers_path.display()))?;
+        Ok(Self {
+            day,
+            name,
+            input_path,
+            expected,
+        })
+    }
+
+    // Runs the fixture through its day's solver, failing with every mismatching part.
+    pub fn check(&self) -> Result<()> {
+        if self.expected.is_empty() {
+            return Ok(());
+        }
+        let day = days::get(self.day)?;
+        let input = std::fs::read_to_string(&self.input_path)
+            .with_context(|| format!("unable to read {}", self.input_path.display()))?;
+        let parts: Vec<_> = self.expected.keys().copied().collect();
+        let solved = day.solve(&input, &parts)?;
+        let mismatches: Vec<_> = solved
+            .parts
+            .into_iter()
+            .filter_map(|solved_part| {
+                let expected = &self.expected[&solved_part.part];
+                match solved_part.answer {
+                    Ok(answer) if &answer == expected => None,
+                    Ok(answer) => Some(format!(
+                        "part {}: expected {expected}, got {answer}",
+                        solved_part.part
+                    )),
+                    Err(err) => Some(format!("part {}: error: {err}", solved_part.part)),
+                }
+            })
+            .collect();
+        anyhow::ensure!(mismatches.is_empty(), "{}", mismatches.join(", "));
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Fixture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day{:02}/{}", self.day, self.name)
+    }
+}
+
+// Every fixture under `dir`, ordered by day and then name.
+pub fn discover(dir: &Path) -> Result<Vec<Fixture>> {
+    let read_dir = |dir: &Path| {
+        std::fs::read_dir(dir).with_context(|| format!("unable to read {}", dir.display()))
+    };
+    let mut fixtures = Vec::new();
+    for entry in read_dir(dir)? {
+        let day_dir = entry?.path();
+        let Some(day) = day_dir
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse::<u8>().ok())
+        else {
+            continue;
+        };
+        for entry in read_dir(&day_dir)? {
+            let input_path = entry?.path();
+            if input_path.extension().is_some_and(|ext| ext == "txt") {
+                fixtures.push(Fixture::load(day, input_path)?);
+            }
+        }
+    }
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(fixtures)
+}
+
+fn parse_answers(input: &str) -> Result<BTreeMap<u8, String>> {
+    let parse_part = context("part 1 or 2", alt((char('1'), char('2'))));
+    let parse_line = map(
+        tuple((parse_part, space1, is_not("\n"))),
+        |(part, _, answer)| (part as u8 - b'0', str::trim(answer).to_string()),
+    );
+    let answers = must_parse_lines(parse_line, input)?;
+    Ok(answers.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() -> Result<()> {
+        let answers = parse_answers("# part answer\n1 6440\n\n2  5905 \n")?;
+        assert_eq!(
+            answers,
+            BTreeMap::from([(1, "6440".to_string()), (2, "5905".to_string())])
+        );
+        assert!(parse_answers("one 1").is_err());
+        let err = parse_answers("1 6440\n\n3 1").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("line 3, column 1: expected part 1 or 2\n"));
+        Ok(())
+    }
+
+    // Runs every fixture in the fixtures directory.
+    #[test]
+    fn test_fixtures() -> Result<()> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_DIR);
+        let fixtures = discover(&dir)?;
+        assert!(!fixtures.is_empty(), "no fixtures in {}", dir.display());
+        let failures: Vec<_> = fixtures
+            .iter()
+            .filter_map(|fixture| {
+                let err = fixture.check().err()?;
+                Some(format!("{fixture}: {err:#}"))
+            })
+            .collect();
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use anyhow::Result;
+
+pub mod polygon;
+
+// Screen orientation is used throughout: x grows to the right and y grows downwards, so `Up` is
+// a step towards row 0 of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // clockwise, starting from `Up`
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_left(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    pub fn delta<T: From<i8>>(self) -> Vec2<T> {
+        use Direction::*;
+        let (x, y) = match self {
+            Up => (0, -1),
+            Down => (0, 1),
+            Left => (-1, 0),
+            Right => (1, 0),
+        };
+        Vec2::new(x.into(), y.into())
+    }
+}
+
+impl std::ops::Not for Direction {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.reverse()
+    }
+}
+
+// Accepts `U/D/L/R`, `^v<>` and compass `N/S/E/W` notations.
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(ch: char) -> Result<Self> {
+        use Direction::*;
+        Ok(match ch {
+            'U' | '^' | 'N' => Up,
+            'D' | 'v' | 'S' => Down,
+            'L' | '<' | 'W' => Left,
+            'R' | '>' | 'E' => Right,
+            unknown => anyhow::bail!("unknown direction: {unknown:?}"),
+        })
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch.try_into(),
+            _ => anyhow::bail!("unknown direction: {s:?}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Direction::*;
+        let ch = match self {
+            Up => '↑',
+            Down => '↓',
+            Left => '←',
+            Right => '→',
+        };
+        write!(f, "{}", ch)
+    }
+}
+
+// The 8-way variant of `Direction`, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    // clockwise, starting from `N`
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    // turns by 45 degrees
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    // turns by 45 degrees
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn delta<T: From<i8>>(self) -> Vec2<T> {
+        use Direction8::*;
+        let (x, y) = match self {
+            N => (0, -1),
+            NE => (1, -1),
+            E => (1, 0),
+            SE => (1, 1),
+            S => (0, 1),
+            SW => (-1, 1),
+            W => (-1, 0),
+            NW => (-1, -1),
+        };
+        Vec2::new(x.into(), y.into())
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Self::N,
+            Direction::Right => Self::E,
+            Direction::Down => Self::S,
+            Direction::Left => Self::W,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Vec2<i64> {
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    // Moves `n` steps in `dir`, returning `None` on overflow.
+    pub fn checked_step(self, dir: Direction, n: i64) -> Option<Self> {
+        let delta = dir.delta::<i64>();
+        Some(Self::new(
+            self.x.checked_add(delta.x.checked_mul(n)?)?,
+            self.y.checked_add(delta.y.checked_mul(n)?)?,
+        ))
+    }
+}
+
+impl Vec2<usize> {
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    // Moves one step in `dir`, returning `None` instead of underflowing past zero.
+    pub fn step(self, dir: Direction) -> Option<Self> {
+        let delta = dir.delta::<isize>();
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+
+    // Moves one step in `dir`, staying within `0..bounds.x` and `0..bounds.y`.
+    pub fn step_within(self, dir: Direction, bounds: Self) -> Option<Self> {
+        self.step(dir)
+            .filter(|pos| pos.x < bounds.x && pos.y < bounds.y)
+    }
+}
+
+// Grid positions are `(row, col)` tuples; rows map to `y` and columns to `x`.
+impl From<(usize, usize)> for Vec2<usize> {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(col, row)
+    }
+}
+
+impl From<Vec2<usize>> for (usize, usize) {
+    fn from(Vec2 { x, y }: Vec2<usize>) -> Self {
+        (y, x)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.delta::<i64>() + dir.reverse().delta(), Vec2::default());
+        }
+        for dir in Direction8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.delta::<i64>(), -dir.reverse().delta());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction8::N.turn_right(), Direction8::NE);
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        use Direction::*;
+        for (notation, expected) in [("U^N", Up), ("DvS", Down), ("L<W", Left), ("R>E", Right)] {
+            for ch in notation.chars() {
+                assert_eq!(Direction::try_from(ch)?, expected);
+            }
+        }
+        assert_eq!("v".parse::<Direction>()?, Direction::Down);
+        assert_eq!("W".parse::<Direction>()?, Direction::Left);
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UD".parse::<Direction>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_step() {
+        let origin = Vec2::<usize>::new(0, 0);
+        assert_eq!(origin.step(Direction::Up), None);
+        assert_eq!(origin.step(Direction::Down), Some(Vec2::new(0, 1)));
+        let bounds = Vec2::new(2, 2);
+        assert_eq!(
+            origin.step_within(Direction::Right, bounds),
+            Some(Vec2::new(1, 0))
+        );
+        assert_eq!(Vec2::new(1, 0).step_within(Direction::Right, bounds), None);
+        assert_eq!(<(usize, usize)>::from(Vec2::new(3, 1)), (1, 3));
+
+        let p = Vec2::<i64>::new(0, 0);
+        assert_eq!(p.checked_step(Direction::Left, 5), Some(Vec2::new(-5, 0)));
+        assert_eq!(
+            Vec2::new(i64::MAX, 0).checked_step(Direction::Right, 1),
+            None
+        );
+        assert_eq!(p.manhattan(Vec2::new(-3, 4)), 7);
+    }
+}
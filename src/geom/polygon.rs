@@ -0,0 +1,162 @@
+// Measurements of simple polygons with integer vertices, given in order as (x, y) pairs. The
+// polygon is closed implicitly, so repeating the first vertex at the end is optional.
+//
+// All arithmetic is done in i128 with overflow checks, so any integer coordinate type can be used.
+
+use anyhow::{Context, Result};
+
+use crate::math;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    // in a y-up coordinate system; clockwise on screen when y grows downwards
+    CounterClockwise,
+    Clockwise,
+    // zero area, e.g. all vertices on one line
+    Degenerate,
+}
+
+// Twice the signed area; positive for counter-clockwise vertices.
+// See: https://en.wikipedia.org/wiki/Shoelace_formula
+pub fn signed_double_area<T>(vertices: &[(T, T)]) -> Result<i128>
+where
+    T: Copy + TryInto<i128>,
+{
+    let points = to_i128(vertices)?;
+    let area = edges(&points).try_fold(0i128, |acc, ((x1, y1), (x2, y2))| {
+        let cross = x1
+            .checked_mul(y2)
+            .zip(x2.checked_mul(y1))
+            .and_then(|(a, b)| a.checked_sub(b));
+        cross
+            .and_then(|cross| acc.checked_add(cross))
+            .context("polygon area overflowed")
+    });
+    area
+}
+
+// Twice the area, which is always an integer for lattice polygons.
+pub fn double_area<T>(vertices: &[(T, T)]) -> Result<u64>
+where
+    T: Copy + TryInto<i128>,
+{
+    let area = signed_double_area(vertices)?.unsigned_abs();
+    area.try_into().context("polygon area overflowed")
+}
+
+pub fn orientation<T>(vertices: &[(T, T)]) -> Result<Orientation>
+where
+    T: Copy + TryInto<i128>,
+{
+    Ok(match signed_double_area(vertices)?.signum() {
+        1 => Orientation::CounterClockwise,
+        -1 => Orientation::Clockwise,
+        _ => Orientation::Degenerate,
+    })
+}
+
+// The number of lattice points on the edges, which for axis-aligned polygons is also the
+// perimeter.
+pub fn boundary_points<T>(vertices: &[(T, T)]) -> Result<u64>
+where
+    T: Copy + TryInto<i128>,
+{
+    let points = to_i128(vertices)?;
+    let perimeter = edges(&points).try_fold(0u64, |acc, ((x1, y1), (x2, y2))| {
+        let dx = x1.checked_sub(x2).context("polygon edge overflowed")?;
+        let dy = y1.checked_sub(y2).context("polygon edge overflowed")?;
+        let n: u64 = math::gcd(dx.unsigned_abs(), dy.unsigned_abs())
+            .try_into()
+            .context("polygon perimeter overflowed")?;
+        acc.checked_add(n).context("polygon perimeter overflowed")
+    });
+    perimeter
+}
+
+// The number of lattice points strictly inside the polygon.
+// See: https://en.wikipedia.org/wiki/Pick%27s_theorem
+pub fn interior_points<T>(vertices: &[(T, T)]) -> Result<u64>
+where
+    T: Copy + TryInto<i128>,
+{
+    // A = i + b/2 - 1  =>  i = (2A - b + 2) / 2
+    let double_area = double_area(vertices)?;
+    let b = boundary_points(vertices)?;
+    let double_interior = double_area
+        .checked_add(2)
+        .context("polygon area overflowed")?
+        .checked_sub(b)
+        .context("boundary is longer than the area allows; is the polygon simple?")?;
+    Ok(double_interior / 2)
+}
+
+fn to_i128<T>(vertices: &[(T, T)]) -> Result<Vec<(i128, i128)>>
+where
+    T: Copy + TryInto<i128>,
+{
+    vertices
+        .iter()
+        .map(|&(x, y)| Some((x.try_into().ok()?, y.try_into().ok()?)))
+        .collect::<Option<_>>()
+        .context("polygon coordinates do not fit in i128")
+}
+
+// consecutive vertex pairs, including the closing edge back to the first vertex
+fn edges(points: &[(i128, i128)]) -> impl Iterator<Item = ((i128, i128), (i128, i128))> + '_ {
+    points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square() -> Result<()> {
+        let square = [(0u32, 0u32), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(double_area(&square)?, 32);
+        assert_eq!(boundary_points(&square)?, 16);
+        assert_eq!(interior_points(&square)?, 9);
+        assert_eq!(orientation(&square)?, Orientation::CounterClockwise);
+
+        // explicitly closed and clockwise
+        let square = [(0i64, 0i64), (0, 4), (4, 4), (4, 0), (0, 0)];
+        assert_eq!(double_area(&square)?, 32);
+        assert_eq!(interior_points(&square)?, 9);
+        assert_eq!(orientation(&square)?, Orientation::Clockwise);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagonal_edges() -> Result<()> {
+        // right triangle with a diagonal hypotenuse through (1, 2) and (2, 1)
+        let triangle = [(0i8, 0i8), (3, 0), (0, 3)];
+        assert_eq!(double_area(&triangle)?, 9);
+        assert_eq!(boundary_points(&triangle)?, 9);
+        assert_eq!(interior_points(&triangle)?, 1);
+        let line = [(0usize, 0usize), (5, 5)];
+        assert_eq!(orientation(&line)?, Orientation::Degenerate);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> Result<()> {
+        let huge = [(0u128, 0u128), (u128::MAX, 0), (0, 1)];
+        assert!(double_area(&huge).is_err());
+        let big = [
+            (0i64, 0i64),
+            (i64::MAX, 0),
+            (i64::MAX, i64::MAX),
+            (0, i64::MAX),
+        ];
+        assert!(double_area(&big).is_err());
+        assert!(signed_double_area(&big).is_ok());
+        // twice the area is exactly u64::MAX
+        let max = [(0u64, 0u64), (u32::MAX as u64, 0), (0, u32::MAX as u64 + 2)];
+        assert_eq!(double_area(&max)?, u64::MAX);
+        assert!(interior_points(&max).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,435 @@
+use anyhow::{Context, Result};
+
+use crate::geom::{Direction, Vec2};
+
+pub type Row = usize;
+pub type Col = usize;
+pub type Pos = (Row, Col);
+
+// (row, col) deltas in up, down, left, right order
+pub const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// A rectangular grid of cells stored in row-major order, indexed by (row, col).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    num_rows: usize,
+    num_cols: usize,
+    cells: Vec<T>,
+}
+
+// Cell types that can be rendered as a single character by `Grid`'s `Display` impl.
+pub trait AsChar {
+    fn as_char(&self) -> char;
+}
+
+impl AsChar for u8 {
+    fn as_char(&self) -> char {
+        *self as char
+    }
+}
+
+impl AsChar for char {
+    fn as_char(&self) -> char {
+        *self
+    }
+}
+
+impl AsChar for bool {
+    fn as_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl Grid<u8> {
+    // Parses a grid of raw bytes, one row per line.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, |ch| {
+            u8::try_from(ch).with_context(|| format!("non-ascii cell: {ch:?}"))
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(num_rows: usize, num_cols: usize, cells: Vec<T>) -> Result<Self> {
+        anyhow::ensure!(
+            cells.len() == num_rows * num_cols,
+            "expected {num_rows}x{num_cols} cells, got {}",
+            cells.len()
+        );
+        Ok(Self {
+            num_rows,
+            num_cols,
+            cells,
+        })
+    }
+
+    pub fn from_elem(num_rows: usize, num_cols: usize, elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            num_rows,
+            num_cols,
+            cells: vec![elem; num_rows * num_cols],
+        }
+    }
+
+    // Parses one row per line, converting every character with `parse_cell`. All rows must have
+    // the same width.
+    pub fn parse_with(input: &str, mut parse_cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut num_rows = 0;
+        let mut num_cols = None;
+        let mut cells = Vec::with_capacity(input.len());
+        for (row, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (col, ch) in line.chars().enumerate() {
+                let cell = parse_cell(ch)
+                    .with_context(|| format!("line {}, column {}", row + 1, col + 1))?;
+                cells.push(cell);
+            }
+            let width = cells.len() - before;
+            let expected = *num_cols.get_or_insert(width);
+            anyhow::ensure!(
+                width == expected,
+                "line {} has {width} columns, expected {expected}",
+                row + 1
+            );
+            num_rows += 1;
+        }
+        let num_cols = num_cols.context("input is empty")?;
+        Self::new(num_rows, num_cols, cells)
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.num_rows && col < self.num_cols
+    }
+
+    // Converts signed coordinates into a position, if they are within the grid.
+    pub fn checked_pos(&self, row: isize, col: isize) -> Option<Pos> {
+        let pos = (row.try_into().ok()?, col.try_into().ok()?);
+        self.contains(pos).then_some(pos)
+    }
+
+    // The position `delta` away from `pos`, if it is within the grid.
+    pub fn step(&self, (row, col): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    // The adjacent position in `dir`, if it is within the grid.
+    pub fn neighbor(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        let bounds = Vec2::new(self.num_cols, self.num_rows);
+        Vec2::from(pos).step_within(dir, bounds).map(Pos::from)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let idx = self.offset(pos);
+            Some(&mut self.cells[idx])
+        } else {
+            None
+        }
+    }
+
+    // Looks up signed coordinates, wrapping them around the edges as if the grid repeated
+    // infinitely in every direction. Panics on an empty grid, which has no cell to wrap onto.
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        assert!(
+            !self.is_empty(),
+            "get_wrapping on an empty {}x{} grid",
+            self.num_rows,
+            self.num_cols
+        );
+        let row = row.rem_euclid(self.num_rows as isize) as usize;
+        let col = col.rem_euclid(self.num_cols as isize) as usize;
+        &self.cells[self.offset((row, col))]
+    }
+
+    /// # Safety
+    ///
+    /// `pos` must be within the grid.
+    pub unsafe fn get_unchecked(&self, pos: Pos) -> &T {
+        self.cells.get_unchecked(self.offset(pos))
+    }
+
+    /// # Safety
+    ///
+    /// `pos` must be within the grid.
+    pub unsafe fn get_unchecked_mut(&mut self, pos: Pos) -> &mut T {
+        let idx = self.offset(pos);
+        self.cells.get_unchecked_mut(idx)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let num_cols = self.num_cols;
+        (0..self.num_rows).flat_map(move |row| (0..num_cols).map(move |col| (row, col)))
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let idx = self.cells.iter().position(pred)?;
+        Some((idx / self.num_cols, idx % self.num_cols))
+    }
+
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4
+            .into_iter()
+            .filter_map(move |delta| self.step(pos, delta))
+    }
+
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8
+            .into_iter()
+            .filter_map(move |delta| self.step(pos, delta))
+    }
+
+    pub fn row(&self, row: Row) -> &[T] {
+        let start = row * self.num_cols;
+        &self.cells[start..start + self.num_cols]
+    }
+
+    pub fn row_mut(&mut self, row: Row) -> &mut [T] {
+        let start = row * self.num_cols;
+        &mut self.cells[start..start + self.num_cols]
+    }
+
+    // Yields `num_rows` slices, even when they are empty.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.num_rows).map(|row| self.row(row))
+    }
+
+    pub fn col(&self, col: Col) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.num_cols, "column {col} out of bounds");
+        // without rows there are no cells to start the column from
+        self.cells
+            .get(col..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(self.num_cols)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.num_cols).map(|col| self.col(col))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.num_cols, self.num_rows, |(row, col)| (col, row))
+    }
+
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        // an empty grid rotates into an empty grid without looking at any cell
+        let last_row = self.num_rows.saturating_sub(1);
+        self.remap(self.num_cols, self.num_rows, |(row, col)| {
+            (last_row - col, row)
+        })
+    }
+
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_col = self.num_cols.saturating_sub(1);
+        self.remap(self.num_cols, self.num_rows, |(row, col)| {
+            (col, last_col - row)
+        })
+    }
+
+    // Builds a new grid whose cell at `pos` is the cell of `self` at `source(pos)`.
+    fn remap(&self, num_rows: usize, num_cols: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..num_rows)
+            .flat_map(|row| (0..num_cols).map(move |col| (row, col)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+        Self {
+            num_rows,
+            num_cols,
+            cells,
+        }
+    }
+
+    fn offset(&self, (row, col): Pos) -> usize {
+        row * self.num_cols + col
+    }
+}
+
+impl<T> std::ops::Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {pos:?} out of bounds for {}x{} grid",
+                self.num_rows, self.num_cols
+            ),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (num_rows, num_cols) = (self.num_rows, self.num_cols);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("position {pos:?} out of bounds for {num_rows}x{num_cols} grid"),
+        }
+    }
+}
+
+impl<T: AsChar> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.as_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"abc
+def
+"#;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let g = Grid::parse(INPUT)?;
+        assert_eq!((g.num_rows(), g.num_cols()), (2, 3));
+        assert_eq!(g[(1, 2)], b'f');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.to_string(), INPUT);
+        assert!(Grid::parse("ab\nc").is_err());
+        assert!(Grid::parse("").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors() -> Result<()> {
+        let g = Grid::parse(INPUT)?;
+        let n4: Vec<_> = g.neighbors4((0, 0)).collect();
+        assert_eq!(n4, vec![(1, 0), (0, 1)]);
+        let n8: Vec<_> = g.neighbors8((1, 1)).collect();
+        assert_eq!(n8, vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]);
+        assert_eq!(g.neighbor((0, 2), Direction::Right), None);
+        assert_eq!(g.neighbor((0, 2), Direction::Down), Some((1, 2)));
+        assert_eq!(g.checked_pos(-1, 0), None);
+        assert_eq!(g.checked_pos(1, 2), Some((1, 2)));
+        assert_eq!(*g.get_wrapping(-1, 4), b'e');
+        Ok(())
+    }
+
+    #[test]
+    fn test_rows_and_cols() -> Result<()> {
+        let g = Grid::parse(INPUT)?;
+        assert_eq!(g.row(1), b"def");
+        let cols: Vec<Vec<u8>> = g.cols().map(|c| c.copied().collect()).collect();
+        assert_eq!(cols, vec![b"ad".to_vec(), b"be".to_vec(), b"cf".to_vec()]);
+        assert_eq!(g.position(|&c| c == b'e'), Some((1, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_transforms() -> Result<()> {
+        let g = Grid::parse(INPUT)?;
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        let upper = g.map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "ABC\nDEF\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty() -> Result<()> {
+        for g in [Grid::from_elem(0, 3, b'.'), Grid::new(2, 0, vec![])?] {
+            let (num_rows, num_cols) = (g.num_rows(), g.num_cols());
+            assert!(g.is_empty());
+            assert_eq!(g.rows().len(), num_rows);
+            assert!(g.rows().all(|row| row.is_empty()));
+            assert_eq!(g.cols().count(), num_cols);
+            assert_eq!(g.to_string(), "\n".repeat(num_rows));
+            assert_eq!(g.get((0, 0)), None);
+            assert_eq!(g.position(|_| true), None);
+
+            let cw = g.rotate_cw();
+            assert_eq!((cw.num_rows(), cw.num_cols()), (num_cols, num_rows));
+            assert_eq!(g.rotate_ccw(), cw);
+            assert_eq!(g.transpose(), cw);
+            let wrapping = std::panic::catch_unwind(|| *g.get_wrapping(0, 0));
+            assert!(wrapping.is_err());
+        }
+        Ok(())
+    }
+}
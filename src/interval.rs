@@ -0,0 +1,333 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use anyhow::Result;
+
+use crate::math::Integer;
+
+// A set of values stored as sorted, disjoint half-open ranges. Overlapping and adjacent ranges are
+// merged as they are inserted, so equal sets always hold the same ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    // start of each range mapped to its end, non-inclusive. ex: the range 5..17 is stored with a
+    // key of 5 and a value of 17
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let Range { mut start, mut end } = range;
+        if let Some((&prev_start, &prev_end)) = self.ranges.range(..=start).next_back() {
+            if prev_end >= start {
+                start = prev_start;
+                end = end.max(prev_end);
+            }
+        }
+        // every range starting inside of, or right after, the new one gets merged into it
+        let merged: Vec<T> = self.ranges.range(start..=end).map(|(&s, _)| s).collect();
+        for s in merged {
+            let e = self.ranges.remove(&s).expect("start was just found");
+            end = end.max(e);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        for overlap in self.overlapping(&range) {
+            let end = self.ranges.remove(&overlap.start).expect("overlap exists");
+            if overlap.start < range.start {
+                self.ranges.insert(overlap.start, range.start);
+            }
+            if end > range.end {
+                self.ranges.insert(range.end, end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // The number of disjoint ranges; see `total_len` for the number of values.
+    pub fn num_ranges(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |acc, (&start, &end)| acc + (end - start))
+    }
+
+    // The smallest value.
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first_key_value().map(|(&start, _)| start)
+    }
+
+    // The largest value.
+    pub fn last(&self) -> Option<T> {
+        self.ranges.last_key_value().map(|(_, &end)| end - T::ONE)
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        other
+            .iter()
+            .flat_map(|range| {
+                self.overlapping(&range)
+                    .into_iter()
+                    .map(move |r| r.start.max(range.start)..r.end.min(range.end))
+            })
+            .collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    // Splits the set into the values below `at` and the ones at or above it.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = self.ranges.clone();
+        let mut above = below.split_off(&at);
+        // a range straddling `at` ends up below and needs to be cut in two
+        if let Some((_, end)) = below.iter_mut().next_back() {
+            if *end > at {
+                above.insert(at, *end);
+                *end = at;
+            }
+        }
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    // The stored ranges that share at least one value with `range`, in order.
+    fn overlapping(&self, range: &Range<T>) -> Vec<Range<T>> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+        // the ranges are disjoint, so their ends are sorted just like their starts
+        let mut overlaps: Vec<_> = self
+            .ranges
+            .range(..range.end)
+            .rev()
+            .take_while(|(_, &end)| end > range.start)
+            .map(|(&start, &end)| start..end)
+            .collect();
+        overlaps.reverse();
+        overlaps
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+// A piecewise-linear map made of segments that shift a source range onto a destination range of
+// the same length. Values outside of every segment map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    // source start mapped to the source end and destination start
+    segments: BTreeMap<T, (T, T)>,
+}
+
+impl<T: Integer> RangeMap<T> {
+    pub fn new() -> Self {
+        Self {
+            segments: BTreeMap::new(),
+        }
+    }
+
+    // Fails when `src` overlaps the source range of an existing segment.
+    pub fn insert(&mut self, src: Range<T>, dest_start: T) -> Result<()> {
+        if let Some(existing) = self.overlapping(&src).first() {
+            anyhow::bail!("segment {src:?} overlaps segment {existing:?}");
+        }
+        if !src.is_empty() {
+            self.segments.insert(src.start, (src.end, dest_start));
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, value: T) -> T {
+        match self.segments.range(..=value).next_back() {
+            Some((&start, &(end, dest_start))) if value < end => dest_start + (value - start),
+            _ => value,
+        }
+    }
+
+    // Maps every value of `range`, which can end up spread over several ranges.
+    pub fn map_range(&self, range: Range<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        self.map_into(range, &mut result);
+        result
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        for range in set.iter() {
+            self.map_into(range, &mut result);
+        }
+        result
+    }
+
+    fn map_into(&self, range: Range<T>, result: &mut IntervalSet<T>) {
+        let mut cur = range.start;
+        for src in self.overlapping(&range) {
+            let (_, dest_start) = self.segments[&src.start];
+            let start = src.start.max(range.start);
+            let end = src.end.min(range.end);
+            // the gap before this segment maps to itself
+            result.insert(cur..start);
+            result.insert(dest_start + (start - src.start)..dest_start + (end - src.start));
+            cur = end;
+        }
+        result.insert(cur..range.end);
+    }
+
+    // The source ranges of the segments sharing at least one value with `range`, in order.
+    fn overlapping(&self, range: &Range<T>) -> Vec<Range<T>> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+        let mut overlaps: Vec<_> = self
+            .segments
+            .range(..range.end)
+            .rev()
+            .take_while(|(_, &(end, _))| end > range.start)
+            .map(|(&start, &(end, _))| start..end)
+            .collect();
+        overlaps.reverse();
+        overlaps
+    }
+}
+
+impl<T: Integer> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<T: Integer>(set: &IntervalSet<T>) -> Vec<Range<T>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let set: IntervalSet<usize> = [1..11, 15..20].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..11, 15..20]);
+        let set: IntervalSet<usize> = [1..11, 1..12].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..12]);
+        let set: IntervalSet<usize> = [1..11, 2..12].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..12]);
+        let set: IntervalSet<usize> = [2..5, 1..11].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..11]);
+        // adjacent ranges and ranges bridging a gap
+        let set: IntervalSet<usize> = [1..3, 3..5, 8..9, 4..8, 20..20].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..9]);
+        assert_eq!(set.total_len(), 8);
+        assert_eq!((set.first(), set.last()), (Some(1), Some(8)));
+        assert!(set.contains(8) && !set.contains(9) && !set.contains(0));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a: IntervalSet<u16> = [0..10, 20..30].into_iter().collect();
+        let b = IntervalSet::from(5..25u16);
+        assert_eq!(ranges(&a.union(&b)), vec![0..30]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![5..10, 20..25]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..5, 25..30]);
+        assert_eq!(ranges(&b.difference(&a)), vec![10..20]);
+        assert!(a.difference(&a).is_empty());
+
+        let (below, above) = a.split_at(25);
+        assert_eq!(ranges(&below), vec![0..10, 20..25]);
+        assert_eq!(ranges(&above), vec![25..30]);
+        let (below, above) = a.split_at(20);
+        assert_eq!((below.num_ranges(), above.num_ranges()), (1, 1));
+        assert_eq!(below.union(&above), a);
+    }
+
+    #[test]
+    fn test_range_map() -> Result<()> {
+        let mut map = RangeMap::new();
+        map.insert(5..15, 100usize)?;
+        assert!(map.insert(0..6, 50).is_err());
+        assert_eq!(
+            (map.get(4), map.get(5), map.get(14), map.get(15)),
+            (4, 100, 109, 15)
+        );
+
+        let mapped = |range| ranges(&map.map_range(range));
+        assert_eq!(mapped(1..5), vec![1..5]);
+        assert_eq!(mapped(15..20), vec![15..20]);
+        assert_eq!(mapped(1..6), vec![1..5, 100..101]);
+        assert_eq!(mapped(1..15), vec![1..5, 100..110]);
+        assert_eq!(mapped(5..15), vec![100..110]);
+        assert_eq!(mapped(6..14), vec![101..109]);
+        assert_eq!(mapped(14..16), vec![15..16, 109..110]);
+        assert_eq!(mapped(13..17), vec![15..17, 108..110]);
+        assert_eq!(mapped(1..17), vec![1..5, 15..17, 100..110]);
+
+        // several segments, mapping downwards
+        map.insert(20..25, 0)?;
+        let set: IntervalSet<usize> = [3..7, 18..22].into_iter().collect();
+        assert_eq!(
+            ranges(&map.map_set(&set)),
+            vec![0..2, 3..5, 18..20, 100..102]
+        );
+        Ok(())
+    }
+}
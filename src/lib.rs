@@ -1,54 +1,25 @@
-use std::path::Path;
-
-use anyhow::Result;
+#![feature(iter_map_windows)]
+#![feature(slice_group_by)]
 
+pub mod answers;
+pub mod bench;
 pub mod bit_set;
+pub mod cycle;
+pub mod days;
+pub mod fixtures;
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod report;
+mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod timing;
+pub mod trace;
 
-pub fn runner<A: std::fmt::Display, B: std::fmt::Display>(
-    part_one: fn(&str) -> Result<A>,
-    part_two: fn(&str) -> Result<B>,
-) -> Result<()> {
-    let mut args = std::env::args();
-    let binary_path = args.next().expect("binary name to be present");
-    let binary_name = Path::new(&binary_path)
-        .file_name()
-        .and_then(|p| p.to_str())
-        .expect("file_name to_str failed");
-    let cmd = args
-        .next()
-        .expect("usage: cmd [1|2] [input_file_path]. cmd is missing");
-    let input_file_path = args
-        .next()
-        .unwrap_or_else(|| format!("inputs/{}.txt", binary_name));
-    let input = std::fs::read_to_string(&input_file_path)
-        .expect(format!("unable to read input file: {input_file_path}").as_str());
-    match cmd.as_str() {
-        "1" => {
-            println!("{}", part_one(&input)?);
-        }
-        "2" => {
-            println!("{}", part_two(&input)?);
-        }
-        u => {
-            anyhow::bail!("unknown cmd: {u}");
-        }
-    };
-    Ok(())
-}
-
-pub fn wait() {
-    let _ = std::io::stdin().read_line(&mut String::new()).unwrap();
-}
-
-pub fn must_parse<'i, P, O>(mut parser: P, input: &'i str) -> Result<O>
-where
-    P: FnMut(&'i str) -> nom::IResult<&'i str, O>,
-{
-    let (rem, out) = parser(input)
-        .map_err(|err: nom::Err<nom::error::Error<&str>>| anyhow::format_err!("{}", err))?;
-    anyhow::ensure!(
-        rem.is_empty() || rem == "\n",
-        "parsing terminated early: {rem}"
-    );
-    Ok(out)
-}
+pub use parse::must_parse;
+pub use runner::{runner, runner_with, DayOption, Failure};
+pub use solution::Solution;
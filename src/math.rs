@@ -0,0 +1,186 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+use anyhow::{Context, Result};
+
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    // |self|; the identity for unsigned types
+    fn abs(self) -> Self;
+}
+
+pub trait SignedInteger: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn abs(self) -> Self {
+                self
+            }
+        }
+    )*};
+    (signed: $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+        }
+
+        impl SignedInteger for $t {}
+    )*};
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+
+// See: https://en.wikipedia.org/wiki/Euclidean_algorithm
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        let temp = b;
+        b = a % b;
+        a = temp
+    }
+    a.abs()
+}
+
+// See: https://en.wikipedia.org/wiki/Least_common_multiple
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).abs().checked_mul(b.abs())
+}
+
+// The lowest common multiple of all the numbers, failing on overflow or when there are none.
+pub fn lcm_all<T: Integer>(nums: impl IntoIterator<Item = T>) -> Result<T> {
+    let mut nums = nums.into_iter();
+    let first = nums.next().context("lcm of no numbers")?;
+    nums.try_fold(first, |acc, n| {
+        checked_lcm(acc, n).with_context(|| format!("lcm of {acc} and {n} overflowed"))
+    })
+}
+
+// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b).
+// See: https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+pub fn extended_gcd<T: SignedInteger>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+// The x in 0..m such that a * x ≡ 1 (mod m), if a and m are coprime.
+pub fn mod_inverse<T: SignedInteger>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a, m);
+    (g == T::ONE).then(|| (x % m + m) % m)
+}
+
+// Solves the system x ≡ r_i (mod m_i), returning (x, m) where m is the lcm of all the moduli and
+// x is the smallest non-negative solution. The moduli don't need to be pairwise coprime; the
+// system fails when two congruences contradict each other.
+// See: https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Result<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+    for (r2, m2) in congruences {
+        anyhow::ensure!(m2 > 0, "modulus must be positive, got {m2}");
+        let (r2, m2) = (r2 as i128, m2 as i128);
+        // x + m * k ≡ r2 (mod m2)  =>  m * k ≡ r2 - x (mod m2)
+        let (g, inv, _) = extended_gcd(m, m2);
+        let diff = r2 - x;
+        anyhow::ensure!(
+            diff % g == 0,
+            "x ≡ {r2} (mod {m2}) contradicts x ≡ {x} (mod {m})"
+        );
+        let k = (diff / g % (m2 / g)) * inv % (m2 / g);
+        x += m * k;
+        m = m / g * m2;
+        x = x.rem_euclid(m);
+        anyhow::ensure!(m <= i64::MAX as i128, "combined modulus overflows i64");
+    }
+    Ok((x as i64, m as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() -> Result<()> {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0usize, 5), 5);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm_all([2usize, 3, 4, 5])?, 60);
+        assert_eq!(checked_lcm(u8::MAX, 2), None);
+        assert!(lcm_all([u64::MAX, 2]).is_err());
+        assert!(lcm_all(Vec::<u64>::new()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-7, 3), (0, 9), (17, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g, "a: {a}, b: {b}");
+        }
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(4i64, 6), None);
+    }
+
+    #[test]
+    fn test_crt() -> Result<()> {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)])?, (23, 105));
+        // moduli sharing a factor
+        assert_eq!(crt([(2, 4), (4, 6)])?, (10, 12));
+        assert!(crt([(1, 4), (2, 6)]).is_err());
+        assert_eq!(crt([])?, (0, 1));
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    character::complete::{line_ending, newline},
+    combinator::{eof, not},
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    sequence::terminated,
+};
+
+pub fn must_parse<'i, P, O>(parser: P, input: &'i str) -> Result<O>
+where
+    P: FnMut(&'i str) -> nom::IResult<&'i str, O, VerboseError<&'i str>>,
+{
+    parse_within(parser, input, input)
+}
+
+// Parses every line of a line based file, such as the answers files, with `parser`. Lines are
+// trimmed, and blank lines as well as lines starting with `#` are skipped. Errors point at the line
+// and column within the whole file.
+pub fn must_parse_lines<'i, P, O>(mut parser: P, input: &'i str) -> Result<Vec<O>>
+where
+    P: FnMut(&'i str) -> nom::IResult<&'i str, O, VerboseError<&'i str>>,
+{
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !(line.is_empty() || line.starts_with('#')))
+        .map(|line| parse_within(&mut parser, input, line))
+        .collect()
+}
+
+// Parses `part`, a slice of `source`, reporting errors by their position in `source`.
+fn parse_within<'i, P, O>(mut parser: P, source: &'i str, part: &'i str) -> Result<O>
+where
+    P: FnMut(&'i str) -> nom::IResult<&'i str, O, VerboseError<&'i str>>,
+{
+    let (rem, out) = parser(part).map_err(|err| match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => ParseError::from_verbose(source, err),
+        nom::Err::Incomplete(_) => ParseError::new(source, "", "incomplete input".to_string()),
+    })?;
+    if !(rem.is_empty() || rem == "\n") {
+        // Point at the first unparsed token rather than the separator the parser backed off from.
+        Err(ParseError::new(
+            source,
+            rem.trim_start(),
+            "parsing terminated early: unexpected trailing input".to_string(),
+        ))?;
+    }
+    Ok(out)
+}
+
+// A newline followed by another, non-blank line. Lists of lines separated by it can `cut` into each
+// line, so that a typo is reported where it is rather than as trailing input: neither the final
+// newline nor a blank line between sections is taken for the start of another item.
+pub fn line_break(input: &str) -> nom::IResult<&str, char, VerboseError<&str>> {
+    terminated(newline, not(alt((eof, line_ending))))(input)
+}
+
+// A parse failure pinned to a line and column of the original input, rendered with the offending
+// line and a caret under the failing position.
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    source_line: String,
+    caret_padding: String,
+    message: String,
+}
+
+impl ParseError {
+    // `at` must be a slice of `input`; anything else points past the end of the input.
+    fn new(input: &str, at: &str, message: String) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + at.len() <= input.len())
+            .unwrap_or(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        let prefix = &input[line_start..offset];
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: prefix.chars().count() + 1,
+            source_line: input[line_start..line_end].to_string(),
+            caret_padding: prefix
+                .chars()
+                .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+                .collect(),
+            message,
+        }
+    }
+
+    fn from_verbose(input: &str, err: VerboseError<&str>) -> Self {
+        // nom records the innermost failure first and appends the enclosing contexts after it.
+        let Some(&(at, _)) = err.errors.first() else {
+            return Self::new(input, input, "parse error".to_string());
+        };
+        // A failed `alt` only reports its last alternative, which reads as if it were the only one
+        // allowed, so name the context wrapping the `alt` instead.
+        let alt = err
+            .errors
+            .iter()
+            .take_while(|(pos, _)| *pos == at)
+            .position(|(_, kind)| matches!(kind, VerboseErrorKind::Nom(ErrorKind::Alt)));
+        let start = match alt.map(|idx| (idx, err.errors.get(idx + 1))) {
+            Some((idx, Some(&(pos, VerboseErrorKind::Context(_))))) if pos == at => idx + 1,
+            _ => 0,
+        };
+        let mut message = match &err.errors[start].1 {
+            VerboseErrorKind::Char(ch) => format!("expected {ch:?}"),
+            VerboseErrorKind::Context(ctx) => format!("expected {ctx}"),
+            VerboseErrorKind::Nom(kind) => format!("expected {}", describe(kind)),
+        };
+        for (_, kind) in &err.errors[start + 1..] {
+            if let VerboseErrorKind::Context(ctx) = kind {
+                message.push_str(&format!(" while parsing {ctx}"));
+            }
+        }
+        if at.is_empty() {
+            // a parser only handed one line of the input stops at the end of that line
+            if at.as_ptr() == input.as_ptr().wrapping_add(input.len()) {
+                message.push_str(", found end of input");
+            } else {
+                message.push_str(", found end of line");
+            }
+        }
+        Self::new(input, at, message)
+    }
+}
+
+fn describe(kind: &ErrorKind) -> &str {
+    match kind {
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a newline",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::Tag => "a literal tag",
+        ErrorKind::IsNot | ErrorKind::TakeWhile1 | ErrorKind::TakeTill1 => {
+            "at least one matching character"
+        }
+        ErrorKind::OneOf => "one of the allowed characters",
+        ErrorKind::SeparatedList | ErrorKind::Many1 => "at least one item",
+        ErrorKind::Alt => "one of the alternatives",
+        ErrorKind::MapRes => "a valid value",
+        ErrorKind::Verify => "a value passing verification",
+        kind => kind.description(),
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {}^", self.caret_padding)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{self, char, newline},
+        combinator::{cut, opt},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+        IResult,
+    };
+
+    use super::*;
+
+    fn pair(input: &str) -> IResult<&str, (u32, u32), VerboseError<&str>> {
+        context(
+            "pair",
+            separated_pair(complete::u32, char(','), complete::u32),
+        )(input)
+    }
+
+    #[test]
+    fn test_parse_ok() {
+        let parsed = must_parse(separated_list1(newline, pair), "1,2\n3,4\n").unwrap();
+        assert_eq!(parsed, vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn test_error_location() {
+        let err = must_parse(separated_pair(pair, newline, pair), "1,2\n3;4\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected ',' while parsing pair\n  |\n2 | 3;4\n  |  ^"
+        );
+    }
+
+    #[test]
+    fn test_error_in_alt() {
+        let unit = context("unit", alt((tag("cm"), tag("in"))));
+        let err = must_parse(separated_pair(complete::u32, char(' '), unit), "12 ft").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected unit\n  |\n1 | 12 ft\n  |    ^"
+        );
+    }
+
+    #[test]
+    fn test_error_at_end_of_input() {
+        let err = must_parse(pair, "\t1,").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected a number while parsing pair\n  |\n1 | \t1,\n  | ^"
+        );
+        let err = must_parse(pair, "1,").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a number while parsing pair, found end of input\n  |\n1 | 1,\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_line_break() {
+        let lines = |input| {
+            must_parse(
+                terminated(separated_list1(line_break, cut(pair)), opt(newline)),
+                input,
+            )
+        };
+        assert_eq!(lines("1,2\n3,4\n").unwrap(), vec![(1, 2), (3, 4)]);
+        let err = lines("1,2\n3;4\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected ',' while parsing pair\n  |\n2 | 3;4\n  |  ^"
+        );
+        // a blank line ends the list instead of failing it
+        let (rem, parsed) = separated_list1(line_break, cut(pair))("1,2\n\n3,4").unwrap();
+        assert_eq!((rem, parsed), ("\n\n3,4", vec![(1, 2)]));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "# x,y\n1,2\n\n  3,4  \n";
+        assert_eq!(must_parse_lines(pair, input).unwrap(), vec![(1, 2), (3, 4)]);
+        let err = must_parse_lines(pair, "1,2\n\n3,\n5,6").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: expected a number while parsing pair, found end of line\n  |\n3 | 3,\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let err = must_parse(separated_list1(newline, pair), "1,2\n3;4\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: parsing terminated early: unexpected trailing input\n  |\n2 | 3;4\n  | ^"
+        );
+    }
+}
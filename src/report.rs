@@ -0,0 +1,150 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::Result;
+use serde::Serialize;
+
+pub const CSV_HEADER: &str = "day,part,answer,elapsed_ns,input_path,error";
+
+// Structured alternatives to the human readable output of the commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // one JSON object per line
+    Json,
+    // a header line followed by one row per record
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => anyhow::bail!("unknown format: {s:?}, expected json or csv"),
+        }
+    }
+}
+
+// The outcome of solving one part of a day. `elapsed` is missing when the part never ran, ex: the
+// input couldn't be read or parsed.
+#[derive(Debug)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String>,
+    pub elapsed: Option<Duration>,
+    pub input_path: String,
+}
+
+// The fields of a `Record` as written to JSON.
+#[derive(Serialize)]
+struct JsonRecord<'r> {
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    elapsed_ns: Option<u64>,
+    input_path: &'r str,
+    error: Option<String>,
+}
+
+impl Record {
+    pub fn to_json(&self) -> String {
+        let (answer, error) = self.answer_and_error();
+        let record = JsonRecord {
+            day: self.day,
+            part: self.part,
+            answer,
+            elapsed_ns: self.elapsed.map(|d| d.as_nanos() as u64),
+            input_path: &self.input_path,
+            error,
+        };
+        serde_json::to_string(&record).expect("records always serialize")
+    }
+
+    // A row matching `CSV_HEADER`; missing values are left empty.
+    pub fn to_csv(&self) -> String {
+        let (answer, error) = self.answer_and_error();
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            answer.unwrap_or_default(),
+            self.elapsed
+                .map(|d| d.as_nanos().to_string())
+                .unwrap_or_default(),
+            self.input_path.clone(),
+            error.unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+
+    fn answer_and_error(&self) -> (Option<String>, Option<String>) {
+        match &self.answer {
+            Ok(answer) => (Some(answer.clone()), None),
+            Err(err) => (None, Some(format!("{err:#}"))),
+        }
+    }
+}
+
+pub fn print(format: Format, records: &[Record]) {
+    match format {
+        Format::Json => {
+            for record in records {
+                println!("{}", record.to_json());
+            }
+        }
+        Format::Csv => {
+            println!("{CSV_HEADER}");
+            for record in records {
+                println!("{}", record.to_csv());
+            }
+        }
+    }
+}
+
+// Quotes fields containing a separator, quote or line break, doubling any quotes inside of them.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats() -> Result<()> {
+        let solved = Record {
+            day: 7,
+            part: 1,
+            answer: Ok("6440".to_string()),
+            elapsed: Some(Duration::from_micros(12)),
+            input_path: "inputs/day07.txt".to_string(),
+        };
+        let failed = Record {
+            day: 7,
+            part: 2,
+            answer: Err(anyhow::format_err!("bad \"card\", line 3")),
+            elapsed: None,
+            input_path: "-".to_string(),
+        };
+        assert_eq!(
+            solved.to_json(),
+            r#"{"day":7,"part":1,"answer":"6440","elapsed_ns":12000,"input_path":"inputs/day07.txt","error":null}"#
+        );
+        let json: serde_json::Value = serde_json::from_str(&failed.to_json())?;
+        assert_eq!(json["answer"], serde_json::Value::Null);
+        assert_eq!(json["error"], "bad \"card\", line 3");
+        assert_eq!(solved.to_csv(), "7,1,6440,12000,inputs/day07.txt,");
+        assert_eq!(failed.to_csv(), r#"7,2,,,-,"bad ""card"", line 3""#);
+        assert_eq!("csv".parse::<Format>()?, Format::Csv);
+        assert!("yaml".parse::<Format>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use std::{io::Read, process::ExitCode};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    answers::{self, Answers, Check},
+    days,
+    report::{self, Format, Record},
+    timing::Samples,
+    trace,
+};
+
+const USAGE: &str =
+    "usage: cmd [--time] [--repeat N] [--check] [--format json|csv] [--trace SPEC] [--step POINTS]
+           [1|2|both] [input_file_path|-]";
+
+#[derive(Debug, Default)]
+struct Options {
+    cmd: Option<String>,
+    input_file_path: Option<String>,
+    time: bool,
+    repeat: usize,
+    check: bool,
+    // prints the answers as bare lines when missing
+    format: Option<Format>,
+    // override the AOC_TRACE and AOC_TRACE_STEP environment variables
+    trace: Option<String>,
+    step: Option<String>,
+}
+
+// Takes an argument the runner doesn't know, along with the ones after it, and returns whether it
+// was one of the day's own options.
+pub type DayOption<'a> = &'a mut dyn FnMut(&str, &mut dyn Iterator<Item = String>) -> Result<bool>;
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>, day_option: DayOption) -> Result<Self> {
+        let mut opts = Self {
+            repeat: 1,
+            ..Default::default()
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--time" => opts.time = true,
+                "--check" => opts.check = true,
+                "--repeat" => {
+                    let n = args.next().context("--repeat requires a value")?;
+                    opts.repeat = n
+                        .parse()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .with_context(|| format!("invalid --repeat value: {n}"))?;
+                    opts.time = true;
+                }
+                "--format" => {
+                    let format = args.next().context("--format requires a value")?;
+                    opts.format = Some(format.parse()?);
+                }
+                "--trace" => opts.trace = Some(args.next().context("--trace requires a value")?),
+                "--step" => opts.step = Some(args.next().context("--step requires a value")?),
+                _ if day_option(&arg, &mut args)? => (),
+                _ if arg.starts_with("--") => anyhow::bail!("unknown option: {arg}"),
+                _ if opts.cmd.is_none() => opts.cmd = Some(arg),
+                _ if opts.input_file_path.is_none() => opts.input_file_path = Some(arg),
+                u => anyhow::bail!("unexpected argument: {u}"),
+            }
+        }
+        Ok(opts)
+    }
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    Usage(anyhow::Error),
+    Io(anyhow::Error),
+    Solver(anyhow::Error),
+    Check(anyhow::Error),
+}
+
+impl Failure {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Failure::Solver(_) => ExitCode::from(1),
+            Failure::Usage(_) => ExitCode::from(2),
+            Failure::Io(_) => ExitCode::from(3),
+            Failure::Check(_) => ExitCode::from(4),
+        }
+    }
+
+    // Prints the error along with its context chain, followed by the usage text for usage errors.
+    pub fn report(&self, usage: &str) {
+        let (Failure::Usage(err) | Failure::Io(err) | Failure::Solver(err) | Failure::Check(err)) =
+            self;
+        eprintln!("error: {err}");
+        for cause in err.chain().skip(1) {
+            eprintln!("caused by: {cause}");
+        }
+        if let Failure::Usage(_) = self {
+            eprintln!("{usage}");
+        }
+    }
+}
+
+pub fn runner(day: u8) -> ExitCode {
+    runner_with(day, &mut |_, _| Ok(false), "")
+}
+
+// Same as `runner`, for a day with options of its own, which `day_usage` describes.
+pub fn runner_with(day: u8, day_option: DayOption, day_usage: &str) -> ExitCode {
+    match run(day, day_option) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            failure.report(&format!("{USAGE}{day_usage}"));
+            failure.exit_code()
+        }
+    }
+}
+
+fn run(day: u8, day_option: DayOption) -> Result<(), Failure> {
+    let day = days::get(day).map_err(Failure::Usage)?;
+    let opts = Options::parse(std::env::args().skip(1), day_option).map_err(Failure::Usage)?;
+    trace::Config::from_env(opts.trace.as_deref(), opts.step.as_deref())
+        .and_then(trace::init)
+        .map_err(Failure::Usage)?;
+    let parts: &[u8] = match opts.cmd.as_deref() {
+        Some("1") => &[1],
+        Some("2") => &[2],
+        Some("both") => &[1, 2],
+        Some(u) => return Err(Failure::Usage(anyhow::format_err!("unknown cmd: {u}"))),
+        None => return Err(Failure::Usage(anyhow::format_err!("cmd is missing"))),
+    };
+    let input_path = resolve_input_path(opts.input_file_path.as_deref(), &day.input_path());
+    let input = match read_input(&input_path) {
+        Ok(input) => input,
+        Err(err) => {
+            // scripts still get a record for every part
+            if let Some(format) = opts.format {
+                let records: Vec<_> = parts
+                    .iter()
+                    .map(|&part| Record {
+                        day: day.day(),
+                        part,
+                        answer: Err(anyhow::format_err!("{err:#}")),
+                        elapsed: None,
+                        input_path: input_path.clone(),
+                    })
+                    .collect();
+                report::print(format, &records);
+            }
+            return Err(Failure::Io(err));
+        }
+    };
+
+    let mut parse_samples = Samples::default();
+    let mut part_samples = vec![Samples::default(); parts.len()];
+    let mut results = Vec::new();
+    let mut parse_error = None;
+    for _ in 0..opts.repeat {
+        results = match day.solve(&input, parts) {
+            Ok(solved) => {
+                parse_samples.push(solved.parse_time);
+                solved
+                    .parts
+                    .into_iter()
+                    .zip(&mut part_samples)
+                    .map(|(solved_part, samples)| {
+                        samples.push(solved_part.elapsed);
+                        (solved_part.part, solved_part.answer)
+                    })
+                    .collect()
+            }
+            Err(err) => {
+                let results = parts
+                    .iter()
+                    .map(|&part| (part, Err(anyhow::format_err!("parse failed: {err:#}"))))
+                    .collect();
+                parse_error = Some(err);
+                results
+            }
+        };
+        // no point in timing a failure
+        if results.iter().any(|(_, answer)| answer.is_err()) {
+            break;
+        }
+    }
+    let records: Vec<_> = results
+        .into_iter()
+        .zip(&part_samples)
+        .map(|((part, answer), samples)| Record {
+            day: day.day(),
+            part,
+            answer,
+            elapsed: samples.median(),
+            input_path: input_path.clone(),
+        })
+        .collect();
+    match opts.format {
+        Some(format) => report::print(format, &records),
+        None => records
+            .iter()
+            .map_while(|record| record.answer.as_ref().ok())
+            .for_each(|answer| println!("{answer}")),
+    }
+    if let Some(err) = parse_error {
+        return Err(Failure::Solver(
+            err.context(format!("day {} parse failed", day.day())),
+        ));
+    }
+    let mut answers = Vec::new();
+    for record in records {
+        let answer = record
+            .answer
+            .with_context(|| format!("day {} part {} failed", day.day(), record.part))
+            .map_err(Failure::Solver)?;
+        answers.push((record.part, answer));
+    }
+    if opts.time {
+        eprintln!("parse: {parse_samples}");
+        for (part, samples) in parts.iter().zip(&part_samples) {
+            eprintln!("part {part}: {samples}");
+        }
+    }
+    if opts.check {
+        let expected = Answers::load(answers::DEFAULT_PATH).map_err(Failure::Io)?;
+        for (part, answer) in &answers {
+            match expected.check(day.day(), *part, answer) {
+                Check::Pass => (),
+                Check::Fail { expected } => {
+                    return Err(Failure::Check(anyhow::format_err!(
+                        "answer mismatch for day {} part {part}\n- {expected}\n+ {answer}",
+                        day.day()
+                    )));
+                }
+                Check::Unknown => eprintln!("no expected answer for day {} part {part}", day.day()),
+            }
+        }
+    }
+    Ok(())
+}
+
+// `-` stands for stdin. Without an explicit path, stdin is used when it is a pipe or redirected
+// from a file, falling back to the day's default input file otherwise.
+fn resolve_input_path(path: Option<&str>, default_path: &str) -> String {
+    match path {
+        Some(path) => path.to_string(),
+        None if stdin_is_piped() => "-".to_string(),
+        None => default_path.to_string(),
+    }
+}
+
+fn read_input(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("unable to read input from stdin")?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("unable to read input file: {path}"))
+    }
+}
+
+#[cfg(unix)]
+fn stdin_is_piped() -> bool {
+    use std::os::unix::fs::FileTypeExt;
+
+    std::fs::metadata("/dev/stdin")
+        .map(|m| m.file_type().is_fifo() || m.file_type().is_file())
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn stdin_is_piped() -> bool {
+    use std::io::IsTerminal;
+
+    !std::io::stdin().is_terminal()
+}
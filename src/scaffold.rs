@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::fixtures;
+
+pub const DEFAULT_TEMPLATE_PATH: &str = "src/days/dayXX.rs.template";
+
+// Creates everything a new day needs, relative to the repository `root`:
+//
+// - src/days/dayNN.rs, rendered from the template
+// - src/bin/dayNN.rs, the binary calling into the runner
+// - the `mod` and `DAYS` entries in src/days.rs
+// - an empty inputs/dayNN.txt and a fixtures/dayNN/1.txt fixture, unless they already exist
+//
+// Refuses to touch anything when the day already exists, and undoes the changes made so far when
+// one of the writes fails. Returns the paths that were written.
+pub fn new_day(root: &Path, day: u8, template_path: &Path) -> Result<Vec<PathBuf>> {
+    anyhow::ensure!((1..=25).contains(&day), "day must be in 1..=25, got {day}");
+    let name = format!("day{day:02}");
+    let solution_path = root.join(format!("src/days/{name}.rs"));
+    let bin_path = root.join(format!("src/bin/{name}.rs"));
+    let days_path = root.join("src/days.rs");
+    let input_path = root.join(format!("inputs/{name}.txt"));
+    let fixture_path = root.join(fixtures::DEFAULT_DIR).join(&name).join("1.txt");
+    let fixture_answers_path = fixture_path.with_extension("answers");
+
+    for path in [&solution_path, &bin_path] {
+        anyhow::ensure!(
+            !path.exists(),
+            "{} already exists, refusing to overwrite it",
+            path.display()
+        );
+    }
+    let template = std::fs::read_to_string(template_path)
+        .with_context(|| format!("unable to read template: {}", template_path.display()))?;
+    let original_days = std::fs::read_to_string(&days_path)
+        .with_context(|| format!("unable to read {}", days_path.display()))?;
+    let days = register(&original_days, day)?;
+
+    let mut written = Vec::new();
+    let mut write = |path: &Path, contents: &str| -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("unable to create {}", dir.display()))?;
+        }
+        std::fs::write(path, contents)
+            .with_context(|| format!("unable to write {}", path.display()))?;
+        written.push(path.to_path_buf());
+        Ok(())
+    };
+    let result = (|| {
+        write(&solution_path, &render(&template, day))?;
+        write(
+            &bin_path,
+            &format!("fn main() -> std::process::ExitCode {{\n    aoc::runner({day})\n}}\n"),
+        )?;
+        write(&days_path, &days)?;
+        for (placeholder, contents) in [
+            (&input_path, ""),
+            (&fixture_path, ""),
+            (&fixture_answers_path, "# part answer\n"),
+        ] {
+            if !placeholder.exists() {
+                write(placeholder, contents)?;
+            }
+        }
+        Ok(())
+    })();
+    if let Err(err) = result {
+        // days.rs is the only file that existed before, everything else was created here
+        for path in &written {
+            let _ = if path == &days_path {
+                std::fs::write(path, &original_days)
+            } else {
+                std::fs::remove_file(path)
+            };
+        }
+        return Err(err);
+    }
+    Ok(written)
+}
+
+// Fills in `{{day}}` with the day number and `{{day_padded}}` with the zero-padded one.
+pub fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{day_padded}}", &format!("{day:02}"))
+        .replace("{{day}}", &day.to_string())
+}
+
+// Adds the `pub mod dayNN;` declaration and the `&dayNN::DayNN,` entry of `DAYS` to the source of
+// days.rs, keeping both sorted by day.
+fn register(days: &str, day: u8) -> Result<String> {
+    let name = format!("day{day:02}");
+    let mod_line = format!("pub mod {name};");
+    let entry_line = format!("    &{name}::Day{day:02},");
+    anyhow::ensure!(
+        !days.lines().any(|line| line == mod_line),
+        "day {day} is already registered in days.rs"
+    );
+
+    let mut lines: Vec<&str> = days.lines().collect();
+    for (new_line, prefix, suffix) in [
+        (&mod_line, "pub mod day", ";"),
+        (&entry_line, "    &day", ","),
+    ] {
+        let day_of = |line: &str| {
+            line.strip_prefix(prefix)?
+                .get(..2)?
+                .parse::<u8>()
+                .ok()
+                .filter(|_| line.ends_with(suffix))
+        };
+        let existing: Vec<_> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| Some((idx, day_of(line)?)))
+            .collect();
+        let idx = match existing.iter().find(|(_, d)| *d > day) {
+            Some((idx, _)) => *idx,
+            None => existing
+                .last()
+                .map(|(idx, _)| idx + 1)
+                .with_context(|| format!("no existing {prefix}NN{suffix} lines in days.rs"))?,
+        };
+        lines.insert(idx, new_line);
+    }
+    let mut days = lines.join("\n");
+    days.push('\n');
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS: &str = "use crate::solution::DynSolution;
+
+pub mod day01;
+pub mod day03;
+
+pub const DAYS: &[&Day] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+";
+
+    #[test]
+    fn test_register() -> Result<()> {
+        let days = register(DAYS, 2)?;
+        assert!(days.contains("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(days.contains("    &day01::Day01,\n    &day02::Day02,\n    &day03::Day03,\n"));
+        let days = register(&days, 25)?;
+        assert!(days.contains("pub mod day03;\npub mod day25;\n\n"));
+        assert!(days.contains("    &day25::Day25,\n];"));
+        assert!(register(&days, 3).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> Result<()> {
+        let template = std::fs::read_to_string(DEFAULT_TEMPLATE_PATH)?;
+        let rendered = render(&template, 7);
+        assert!(rendered.contains("pub struct Day07;"));
+        assert!(rendered.contains("const DAY: u8 = 7;"));
+        assert!(rendered.contains("day 7 part 1 not implemented"));
+        assert!(!rendered.contains("todo!"));
+        assert!(!rendered.contains("{{"));
+        assert!(!rendered.contains('\t'));
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_day() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/days"))?;
+        std::fs::write(root.join("src/days.rs"), DAYS)?;
+        let template = Path::new(DEFAULT_TEMPLATE_PATH);
+
+        let written = new_day(&root, 2, template)?;
+        assert_eq!(written.len(), 6);
+        assert!(root.join("src/days/day02.rs").exists());
+        assert!(root.join("src/bin/day02.rs").exists());
+        assert!(root.join("inputs/day02.txt").exists());
+        assert!(root.join("fixtures/day02/1.txt").exists());
+        assert!(root.join("fixtures/day02/1.answers").exists());
+
+        // existing days are left alone
+        std::fs::write(root.join("src/days/day02.rs"), "edited")?;
+        assert!(new_day(&root, 2, template).is_err());
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/days/day02.rs"))?,
+            "edited"
+        );
+        assert!(new_day(&root, 26, template).is_err());
+        assert!(new_day(&root, 4, Path::new("missing.template")).is_err());
+        assert!(!root.join("src/days/day04.rs").exists());
+
+        // a failed write undoes the ones before it
+        let days = std::fs::read_to_string(root.join("src/days.rs"))?;
+        std::fs::write(root.join("fixtures/day05"), "not a directory")?;
+        assert!(new_day(&root, 5, template).is_err());
+        assert!(!root.join("src/days/day05.rs").exists());
+        assert!(!root.join("src/bin/day05.rs").exists());
+        assert!(!root.join("inputs/day05.txt").exists());
+        assert_eq!(std::fs::read_to_string(root.join("src/days.rs"))?, days);
+
+        std::fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
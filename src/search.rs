@@ -0,0 +1,307 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::grid::{Grid, Pos};
+
+// A cheapest path found by `dijkstra` or `a_star`, including both of its end states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+// Cheapest path from any of `starts` to a state satisfying `is_goal`. `neighbors` yields the
+// states reachable from a state along with the cost of the move, which must not be negative.
+// Returns `None` when no goal state is reachable.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    a_star(starts, neighbors, |_| C::default(), is_goal)
+}
+
+// Same as `dijkstra`, but explores states in order of cost so far plus `heuristic`, which must
+// never overestimate the remaining cost to a goal for the result to be the cheapest path.
+// See: https://en.wikipedia.org/wiki/A*_search_algorithm
+pub fn a_star<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // every state seen so far, with the cheapest known cost to reach it and the index of the
+    // state it was reached from
+    let mut states: Vec<(S, C, Option<usize>)> = Vec::new();
+    let mut indices: HashMap<S, usize> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(e) = indices.entry(start.clone()) {
+            e.insert(states.len());
+            queue.push(Reverse((heuristic(&start), C::default(), states.len())));
+            states.push((start, C::default(), None));
+        }
+    }
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        if cost > states[idx].1 {
+            // a cheaper way to this state was queued after this entry
+            continue;
+        }
+        if is_goal(&states[idx].0) {
+            return Some(Path {
+                cost,
+                states: reconstruct(&states, idx),
+            });
+        }
+        for (next, step_cost) in neighbors(&states[idx].0) {
+            let next_cost = cost + step_cost;
+            let next_idx = match indices.entry(next) {
+                Entry::Vacant(e) => {
+                    let next_idx = states.len();
+                    states.push((e.key().clone(), next_cost, Some(idx)));
+                    e.insert(next_idx);
+                    next_idx
+                }
+                Entry::Occupied(e) => {
+                    let next_idx = *e.get();
+                    if states[next_idx].1 <= next_cost {
+                        continue;
+                    }
+                    states[next_idx].1 = next_cost;
+                    states[next_idx].2 = Some(idx);
+                    next_idx
+                }
+            };
+            let estimate = next_cost + heuristic(&states[next_idx].0);
+            queue.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+    None
+}
+
+fn reconstruct<S: Clone, C>(states: &[(S, C, Option<usize>)], mut idx: usize) -> Vec<S> {
+    let mut path = vec![states[idx].0.clone()];
+    while let Some(parent) = states[idx].2 {
+        path.push(states[parent].0.clone());
+        idx = parent;
+    }
+    path.reverse();
+    path
+}
+
+// Breadth-first traversal from all of `starts` at once, yielding every reachable state once along
+// with its distance from the nearest start, in order of increasing distance.
+pub struct Bfs<S, N> {
+    queue: VecDeque<(S, usize)>,
+    seen: HashSet<S>,
+    neighbors: N,
+}
+
+impl<S, N, I> Bfs<S, N>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    pub fn new(starts: impl IntoIterator<Item = S>, neighbors: N) -> Self {
+        let mut seen = HashSet::new();
+        let queue = starts
+            .into_iter()
+            .filter(|start| seen.insert(start.clone()))
+            .map(|start| (start, 0))
+            .collect();
+        Self {
+            queue,
+            seen,
+            neighbors,
+        }
+    }
+
+    // Only the states at most `max_steps` away.
+    pub fn within(self, max_steps: usize) -> impl Iterator<Item = (S, usize)> {
+        self.take_while(move |(_, steps)| *steps <= max_steps)
+    }
+}
+
+impl<S, N, I> Iterator for Bfs<S, N>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (state, steps) = self.queue.pop_front()?;
+        for next in (self.neighbors)(&state) {
+            if self.seen.insert(next.clone()) {
+                self.queue.push_back((next, steps + 1));
+            }
+        }
+        Some((state, steps))
+    }
+}
+
+// Distance to every state reachable from `starts`.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    Bfs::new(starts, neighbors).collect()
+}
+
+// Dense variant of `bfs` over the 4-neighbours of a grid, only entering cells that are
+// `passable`. Unreachable cells are `None`.
+pub fn grid_bfs<T>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Pos>,
+    mut passable: impl FnMut(&T) -> bool,
+) -> Grid<Option<usize>> {
+    let mut distances = grid.map(|_| None);
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if distances[start].is_none() {
+            distances[start] = Some(0);
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((pos, steps)) = queue.pop_front() {
+        for next in grid.neighbors4(pos) {
+            if distances[next].is_none() && passable(&grid[next]) {
+                distances[next] = Some(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+// All the positions 4-connected to `start` through `passable` cells, including `start` itself.
+pub fn flood_fill<T>(grid: &Grid<T>, start: Pos, passable: impl FnMut(&T) -> bool) -> Vec<Pos> {
+    grid_bfs(grid, [start], passable)
+        .enumerate()
+        .filter_map(|(pos, steps)| steps.map(|_| pos))
+        .collect()
+}
+
+// Labels every cell with the index of its region, a 4-connected area of equal cells. Returns the
+// labels along with the number of regions.
+pub fn regions<T: PartialEq>(grid: &Grid<T>) -> (Grid<usize>, usize) {
+    let mut labels = grid.map(|_| None);
+    let mut num_regions = 0;
+    let mut queue = VecDeque::new();
+    for start in grid.positions() {
+        if labels[start].is_some() {
+            continue;
+        }
+        labels[start] = Some(num_regions);
+        queue.push_back(start);
+        while let Some(pos) = queue.pop_front() {
+            for next in grid.neighbors4(pos) {
+                if labels[next].is_none() && grid[next] == grid[start] {
+                    labels[next] = Some(num_regions);
+                    queue.push_back(next);
+                }
+            }
+        }
+        num_regions += 1;
+    }
+    let labels = labels.map(|label| label.expect("every cell is labelled"));
+    (labels, num_regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -1-> 1 -1-> 2 -1-> 3
+    //  \____________5_____/
+    fn neighbors(n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra([0], neighbors, |n| *n == 3);
+        assert_eq!(
+            path,
+            Some(Path {
+                cost: 3,
+                states: vec![0, 1, 2, 3]
+            })
+        );
+        assert_eq!(dijkstra([0], neighbors, |n| *n == 4), None);
+        let path = dijkstra([3], neighbors, |n| *n == 3).unwrap();
+        assert_eq!((path.cost, path.states), (0, vec![3]));
+    }
+
+    #[test]
+    fn test_a_star() {
+        // walk a 5x5 grid from a corner to the opposite one
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y))
+                .map(|pos| (pos, 1))
+        };
+        let heuristic = |&(x, y): &(i32, i32)| (4 - x) + (4 - y);
+        let path = a_star([(0, 0)], neighbors, heuristic, |pos| *pos == (4, 4)).unwrap();
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.states.len(), 9);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&(4, 4)));
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbors = |n: &u32| neighbors(n).into_iter().map(|(n, _)| n);
+        let distances = bfs([0], neighbors);
+        assert_eq!(distances, HashMap::from([(0, 0), (1, 1), (2, 2), (3, 1)]));
+        let within: Vec<_> = Bfs::new([1, 2], neighbors).within(0).collect();
+        assert_eq!(within, vec![(1, 0), (2, 0)]);
+        let within: Vec<_> = Bfs::new([0], neighbors).within(1).map(|(n, _)| n).collect();
+        assert_eq!(within, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_grid_bfs() -> anyhow::Result<()> {
+        let grid = Grid::parse("..#.\n.##.\n....")?;
+        let distances = grid_bfs(&grid, [(0, 0)], |&c| c == b'.');
+        assert_eq!(distances[(0, 3)], Some(7));
+        assert_eq!(distances[(0, 2)], None);
+        let both_ends = grid_bfs(&grid, [(0, 0), (0, 3)], |&c| c == b'.');
+        assert_eq!(both_ends[(2, 2)], Some(3));
+
+        assert_eq!(flood_fill(&grid, (1, 1), |&c| c == b'#').len(), 3);
+        let (labels, num_regions) = regions(&grid);
+        assert_eq!(num_regions, 2);
+        assert_eq!(labels[(0, 2)], labels[(1, 1)]);
+        assert_ne!(labels[(0, 0)], labels[(0, 2)]);
+        Ok(())
+    }
+}
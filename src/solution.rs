@@ -0,0 +1,90 @@
+use std::{fmt::Display, time::Duration};
+
+use anyhow::Result;
+
+use crate::timing;
+
+// A puzzle solution that parses its input once and solves both parts from the parsed form. The
+// parsed input may borrow from the raw puzzle input.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Input<'i>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Self::Input<'i>>;
+    fn part_one(&self, input: &Self::Input<'_>) -> Result<Self::PartOne>;
+    fn part_two(&self, input: &Self::Input<'_>) -> Result<Self::PartTwo>;
+
+    fn solve_part_one(&self, input: &str) -> Result<Self::PartOne> {
+        self.part_one(&self.parse(input)?)
+    }
+
+    fn solve_part_two(&self, input: &str) -> Result<Self::PartTwo> {
+        self.part_two(&self.parse(input)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub parts: Vec<SolvedPart>,
+}
+
+#[derive(Debug)]
+pub struct SolvedPart {
+    pub part: u8,
+    pub answer: Result<String>,
+    pub elapsed: Duration,
+}
+
+// Object safe view of a `Solution`, used by the day registry and the tools built on top of it.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    // Fails only when the input cannot be parsed, errors from individual parts are reported in
+    // the returned `SolvedPart`s.
+    fn solve(&self, input: &str, parts: &[u8]) -> Result<Solved>;
+
+    fn name(&self) -> String {
+        format!("day{:02}", self.day())
+    }
+
+    fn input_path(&self) -> String {
+        format!("inputs/{}.txt", self.name())
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn solve(&self, input: &str, parts: &[u8]) -> Result<Solved> {
+        let (parsed, parse_time) = timing::measure(|| self.parse(input));
+        let parsed = parsed?;
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let (answer, elapsed) = timing::measure(|| match part {
+                    1 => self.part_one(&parsed).map(|a| a.to_string()),
+                    2 => self.part_two(&parsed).map(|a| a.to_string()),
+                    p => Err(anyhow::format_err!("unknown part: {p}")),
+                });
+                SolvedPart {
+                    part,
+                    answer,
+                    elapsed,
+                }
+            })
+            .collect();
+        Ok(Solved { parse_time, parts })
+    }
+}
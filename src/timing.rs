@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let out = f();
+    (out, start.elapsed())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+    pub fn push(&mut self, sample: Duration) {
+        self.0.push(sample);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.0.iter().min().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.0.iter().max().copied()
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        let total: Duration = self.0.iter().sum();
+        Some(total / u32::try_from(self.len()).ok().filter(|&n| n > 0)?)
+    }
+
+    // the sample standard deviation, which needs at least two samples
+    pub fn std_dev(&self) -> Option<Duration> {
+        let mean = self.mean()?.as_secs_f64();
+        let n = self.len().checked_sub(1).filter(|&n| n > 0)? as f64;
+        let variance = self
+            .0
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        Some(Duration::from_secs_f64(variance.sqrt()))
+    }
+
+    // for an even number of samples, the lower of the two middle samples is reported
+    pub fn median(&self) -> Option<Duration> {
+        let mut sorted = self.0.clone();
+        sorted.sort_unstable();
+        sorted.get(sorted.len().saturating_sub(1) / 2).copied()
+    }
+}
+
+impl std::iter::FromIterator<Duration> for Samples {
+    fn from_iter<T: IntoIterator<Item = Duration>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl std::fmt::Display for Samples {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.min(), self.median(), self.max()) {
+            (Some(min), Some(median), Some(max)) if self.len() > 1 => write!(
+                f,
+                "min {min:?}, median {median:?}, max {max:?} ({} runs)",
+                self.len()
+            ),
+            (Some(only), ..) => write!(f, "{only:?}"),
+            _ => write!(f, "no samples"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Samples = [5, 1, 3, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        assert_eq!(samples.min(), Some(Duration::from_millis(1)));
+        assert_eq!(samples.median(), Some(Duration::from_millis(2)));
+        assert_eq!(samples.max(), Some(Duration::from_millis(5)));
+        assert_eq!(samples.mean(), Some(Duration::from_micros(2750)));
+        let std_dev = samples.std_dev().unwrap().as_secs_f64();
+        assert!((std_dev - 0.00170782).abs() < 1e-6, "std dev: {std_dev}");
+        assert_eq!(Samples::default().median(), None);
+        assert_eq!(Samples::default().mean(), None);
+        assert_eq!(Samples::from_iter([Duration::ZERO]).std_dev(), None);
+    }
+}
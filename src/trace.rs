@@ -0,0 +1,251 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Arguments},
+    io::BufRead,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        OnceLock,
+    },
+};
+
+use anyhow::{Context, Result};
+
+pub const ENV_VAR: &str = "AOC_TRACE";
+pub const STEP_ENV_VAR: &str = "AOC_TRACE_STEP";
+
+// Prints a debug message to stderr when tracing is enabled for the calling module at `$level`, one
+// of the `Level` variants. The target of a trace point is the last segment of its module path,
+// ex: `day17` for the solver in days/day17.rs.
+//
+// Trace points can be named with `point = "name"`, so that step mode can pause on them:
+//
+//     crate::trace!(Debug, point = "path", "{}", PathPrinter(p, &path));
+//
+// The message is only formatted when the trace point is enabled.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, point = $point:expr, $($arg:tt)+) => {{
+        let level = $crate::trace::Level::$level;
+        let target = $crate::trace::target(module_path!());
+        if $crate::trace::enabled(target, level) {
+            $crate::trace::emit(level, target, Some($point), format_args!($($arg)+));
+        }
+    }};
+    ($level:ident, $($arg:tt)+) => {{
+        let level = $crate::trace::Level::$level;
+        let target = $crate::trace::target(module_path!());
+        if $crate::trace::enabled(target, level) {
+            $crate::trace::emit(level, target, None, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => anyhow::bail!("unknown trace level: {s:?}"),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        f.write_str(name)
+    }
+}
+
+// Which trace points print, and which of them pause until enter is pressed.
+//
+// Levels are given as comma separated directives: a bare level applies to every target and
+// `target=level` overrides it for one target, ex: `info,day17=trace`. The level `off` silences a
+// target.
+//
+// Step points are comma separated as well: `day17` pauses on every enabled trace point of day17,
+// and `day17:path` only on the ones named `path`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    default: Option<Level>,
+    targets: BTreeMap<String, Option<Level>>,
+    // target, and the name of the trace point when only that one pauses
+    steps: Vec<(String, Option<String>)>,
+}
+
+impl Config {
+    pub fn parse(levels: &str, steps: &str) -> Result<Self> {
+        let mut config = Self::default();
+        let parse_level = |level: &str| match level {
+            "off" => Ok(None),
+            level => level.parse().map(Some),
+        };
+        for directive in levels.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    config
+                        .targets
+                        .insert(target.trim().to_string(), parse_level(level.trim())?);
+                }
+                None => config.default = parse_level(directive)?,
+            }
+        }
+        for step in steps.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            config.steps.push(match step.split_once(':') {
+                Some((target, point)) => (target.to_string(), Some(point.to_string())),
+                None => (step.to_string(), None),
+            });
+        }
+        Ok(config)
+    }
+
+    // Reads the levels from AOC_TRACE and the step points from AOC_TRACE_STEP, either of which can
+    // be overridden, ex: by command line flags.
+    pub fn from_env(levels: Option<&str>, steps: Option<&str>) -> Result<Self> {
+        let var = |name: &str, value: Option<&str>| {
+            value
+                .map(str::to_string)
+                .unwrap_or_else(|| std::env::var(name).unwrap_or_default())
+        };
+        let (levels, steps) = (var(ENV_VAR, levels), var(STEP_ENV_VAR, steps));
+        Self::parse(&levels, &steps)
+            .with_context(|| format!("invalid trace config: {levels:?}, steps: {steps:?}"))
+    }
+
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let max = match self.targets.get(target) {
+            Some(max) => *max,
+            None => self.default,
+        };
+        max.is_some_and(|max| level <= max)
+    }
+
+    fn pauses_on(&self, target: &str, point: Option<&str>) -> bool {
+        self.steps.iter().any(|(t, p)| {
+            t == target
+                && match p {
+                    Some(p) => point == Some(p.as_str()),
+                    None => true,
+                }
+        })
+    }
+
+    // The most verbose level enabled for any target.
+    fn max_level(&self) -> Option<Level> {
+        self.targets
+            .values()
+            .copied()
+            .chain([self.default])
+            .max()
+            .flatten()
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+// `Level` of the most verbose enabled target, or 0 when tracing is off; lets disabled trace points
+// bail out without looking at the config
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(u8::MAX);
+
+// Sets up tracing for the rest of the process. Without a call to `init`, the config is read from the
+// environment on first use. Fails when tracing was already set up.
+pub fn init(config: Config) -> Result<()> {
+    let max_level = config.max_level().map_or(0, |level| level as u8);
+    CONFIG
+        .set(config)
+        .map_err(|_| anyhow::format_err!("tracing is already initialized"))?;
+    MAX_LEVEL.store(max_level, Ordering::Relaxed);
+    Ok(())
+}
+
+fn config() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let config = Config::from_env(None, None).unwrap_or_else(|err| {
+            eprintln!("tracing disabled, {err:#}");
+            Config::default()
+        });
+        MAX_LEVEL.store(
+            config.max_level().map_or(0, |level| level as u8),
+            Ordering::Relaxed,
+        );
+        config
+    })
+}
+
+// The target of a module: the last segment of its path.
+pub fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) && config().enabled(target, level)
+}
+
+pub fn emit(level: Level, target: &str, point: Option<&str>, args: Arguments<'_>) {
+    match point {
+        Some(point) => eprintln!("[{level} {target}:{point}] {args}"),
+        None => eprintln!("[{level} {target}] {args}"),
+    }
+    if config().pauses_on(target, point) {
+        eprint!("paused, press enter to continue");
+        wait_for_enter();
+    }
+}
+
+// Reads from the terminal rather than stdin, which might be the puzzle input.
+fn wait_for_enter() {
+    let mut line = String::new();
+    let _ = match std::fs::File::open("/dev/tty") {
+        Ok(tty) => std::io::BufReader::new(tty).read_line(&mut line),
+        Err(_) => std::io::stdin().read_line(&mut line),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config() -> Result<()> {
+        let config = Config::parse("info, day17=trace,day12=off", "day17:path,day05")?;
+        assert!(config.enabled("day01", Level::Info));
+        assert!(!config.enabled("day01", Level::Debug));
+        assert!(config.enabled("day17", Level::Trace));
+        assert!(!config.enabled("day12", Level::Error));
+        assert_eq!(config.max_level(), Some(Level::Trace));
+
+        assert!(config.pauses_on("day17", Some("path")));
+        assert!(!config.pauses_on("day17", Some("state")));
+        assert!(!config.pauses_on("day17", None));
+        assert!(config.pauses_on("day05", None));
+        assert!(config.pauses_on("day05", Some("seeds")));
+
+        let off = Config::parse("", "")?;
+        assert!(!off.enabled("day17", Level::Error));
+        assert_eq!(off.max_level(), None);
+        assert!(Config::parse("loud", "").is_err());
+        assert!(Config::parse("day17=", "").is_err());
+        assert_eq!(target("aoc::days::day17"), "day17");
+        Ok(())
+    }
+}